@@ -0,0 +1,37 @@
+use components::glyph::Colour;
+use entities::item::Item;
+use rand::seq::SliceRandom;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+// One of the looks an unidentified item can take, such as a murky potion
+#[derive(Deserialize, Debug, Clone)]
+pub struct Appearance {
+    pub name: String,
+    pub description: String,
+    pub colour: Colour,
+}
+
+// Deal the appearances out to unidentified items at random, so that what each kind of item looks
+// like has to be learnt again every run. Any left over once the appearances run out are known from
+// the start
+pub fn disguise(items: &mut HashMap<String, Item>, appearances: &HashMap<String, Vec<Appearance>>) {
+    let mut rng = rand::thread_rng();
+
+    for (kind, looks) in appearances {
+        let mut looks = looks.iter().collect::<Vec<_>>();
+        looks.shuffle(&mut rng);
+        let mut looks = looks.into_iter();
+
+        for item in items.values_mut().filter(|item| {
+            item.unidentified
+                .as_ref()
+                .is_some_and(|unidentified| unidentified.kind == *kind)
+        }) {
+            match looks.next() {
+                Some(look) => item.disguise(look),
+                None => item.unidentified = None,
+            }
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use interface::screen::Rect;
+use vector::Vector;
+
+// Fraction of the distance to its target the camera covers each frame while following
+const FOLLOW_SMOOTHING: i32 = 3;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CameraMode {
+    Follow,
+    FreeLook,
+}
+
+// Which part of the map is shown in the viewport
+#[derive(Debug, Copy, Clone)]
+pub struct Camera {
+    pub centre: Vector<i32>,
+    pub viewport: Rect,
+    pub map_size: Vector<i32>,
+    pub mode: CameraMode,
+}
+
+impl Camera {
+    pub fn new(viewport: Rect, map_size: Vector<i32>) -> Self {
+        Self {
+            centre: Vector::default(),
+            viewport,
+            map_size,
+            mode: CameraMode::Follow,
+        }
+    }
+
+    // Move part of the way towards a target, unless looking around freely
+    pub fn follow(&mut self, target: Vector<i32>) {
+        if self.mode == CameraMode::Follow {
+            let step = |distance: i32| match distance {
+                0 => 0,
+                d if d.abs() <= FOLLOW_SMOOTHING => d.signum(),
+                d => d / FOLLOW_SMOOTHING,
+            };
+            let offset = target - self.centre;
+
+            self.centre += Vector(step(offset.0), step(offset.1));
+            self.clamp();
+        }
+    }
+
+    // Centre on a target immediately
+    pub fn snap(&mut self, target: Vector<i32>) {
+        self.centre = target;
+        self.clamp();
+    }
+
+    pub fn pan(&mut self, offset: Vector<i32>) {
+        self.centre += offset;
+        self.clamp();
+    }
+
+    pub fn toggle_free_look(&mut self) {
+        self.mode = match self.mode {
+            CameraMode::Follow => CameraMode::FreeLook,
+            CameraMode::FreeLook => CameraMode::Follow,
+        };
+    }
+
+    pub fn set_viewport(&mut self, viewport: Rect) {
+        self.viewport = viewport;
+        self.clamp();
+    }
+
+    // Keep the viewport from showing past the edges of the map
+    fn clamp(&mut self) {
+        let clamp_axis = |centre: i32, half: i32, scale: i32, size: i32| {
+            if scale >= size {
+                size / 2
+            } else {
+                centre.max(half).min(size - (scale - half))
+            }
+        };
+        let half = (self.viewport.scale.0 / 2, self.viewport.scale.1 / 2);
+
+        self.centre = Vector(
+            clamp_axis(
+                self.centre.0,
+                half.0,
+                self.viewport.scale.0,
+                self.map_size.0,
+            ),
+            clamp_axis(
+                self.centre.1,
+                half.1,
+                self.viewport.scale.1,
+                self.map_size.1,
+            ),
+        );
+    }
+
+    // The map position shown at a point on the screen
+    pub fn screen_to_world(&self, screen: (i32, i32)) -> Vector<i32> {
+        let centre = self.viewport.centre();
+
+        self.centre + Vector(screen.0 - centre.0, screen.1 - centre.1)
+    }
+
+    // The point on the screen a map position is shown at
+    pub fn world_to_screen(&self, point: Vector<i32>) -> (i32, i32) {
+        let centre = self.viewport.centre();
+        let offset = point - self.centre;
+
+        (centre.0 + offset.0, centre.1 + offset.1)
+    }
+
+    // Whether a map position falls within the viewport
+    pub fn shows(&self, point: Vector<i32>) -> bool {
+        self.viewport.contains(self.world_to_screen(point))
+    }
+}
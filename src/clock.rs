@@ -0,0 +1,31 @@
+// The game clock. Actions spend half turns on it, and a turn passes once two have been spent
+#[derive(Debug, Default, Copy, Clone)]
+pub struct Clock {
+    pub turn: u64,
+    owed: u32, // Half turns spent on actions that have yet to pass
+}
+
+impl Clock {
+    pub fn tick(&mut self) {
+        self.turn += 1;
+    }
+
+    // Spend time on an action, which takes a number of half turns to pass
+    pub fn spend(&mut self, halves: u32) {
+        self.owed += halves;
+    }
+
+    // Let a turn pass if enough time has been spent on it, so that quick actions take part of
+    // a turn and slow ones take several
+    pub fn pass(&mut self) {
+        if self.owed >= 2 {
+            self.owed -= 2;
+            self.tick();
+        }
+    }
+
+    // The number of turns that have passed since the given turn
+    pub fn since(&self, turn: u64) -> u64 {
+        self.turn.saturating_sub(turn)
+    }
+}
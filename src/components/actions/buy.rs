@@ -0,0 +1,7 @@
+use legion::Entity;
+
+#[derive(Debug, Clone)]
+pub struct Buy {
+    pub trader: Entity,
+    pub entity: Entity,
+}
@@ -0,0 +1,6 @@
+use legion::Entity;
+
+#[derive(Debug, Clone)]
+pub struct Consume {
+    pub entity: Entity,
+}
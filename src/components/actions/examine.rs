@@ -0,0 +1,6 @@
+use legion::Entity;
+
+#[derive(Debug, Clone)]
+pub struct Examine {
+    pub entity: Entity,
+}
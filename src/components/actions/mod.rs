@@ -1,2 +1,10 @@
+pub mod buy;
+pub mod consume;
+pub mod craft;
+pub mod examine;
 pub mod grab;
+pub mod operate;
+pub mod sell;
+pub mod stow;
+pub mod take;
 pub mod throw;
@@ -0,0 +1,6 @@
+use legion::Entity;
+
+#[derive(Debug, Clone)]
+pub struct Operate {
+    pub entity: Entity,
+}
@@ -0,0 +1,6 @@
+use legion::Entity;
+
+#[derive(Debug, Clone)]
+pub struct Stow {
+    pub container: Option<Entity>, // Where to put what is held, or else whatever is worn for storage
+}
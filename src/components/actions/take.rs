@@ -0,0 +1,7 @@
+use legion::Entity;
+
+#[derive(Debug, Clone)]
+pub struct Take {
+    pub entity: Entity,
+    pub container: Entity,
+}
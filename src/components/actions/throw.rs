@@ -1,6 +1,8 @@
 use components::direction::Direction;
+use legion::Entity;
 
 #[derive(Debug, Clone)]
 pub struct Throw {
     pub direction: Direction,
+    pub rest: Option<Entity>, // What is left in hand when only part of a stack is thrown
 }
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+
+// The attribute every other is measured against, which changes nothing
+pub const AVERAGE: u32 = 10;
+
+// Temporary changes to attributes, such as from status effects
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Modifiers {
+    pub strength: i32,
+    pub agility: i32,
+    pub perception: i32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub struct Attributes {
+    pub strength: u32,
+    pub agility: u32,
+    pub perception: u32,
+    #[serde(default)]
+    pub modifiers: Modifiers,
+}
+
+impl Default for Attributes {
+    fn default() -> Self {
+        Self {
+            strength: AVERAGE,
+            agility: AVERAGE,
+            perception: AVERAGE,
+            modifiers: Modifiers::default(),
+        }
+    }
+}
+
+fn modify(base: u32, modifier: i32) -> u32 {
+    (base as i32 + modifier).max(1) as u32
+}
+
+impl Attributes {
+    // The attributes as they currently stand, after any modifiers
+    pub fn effective_strength(&self) -> u32 {
+        modify(self.strength, self.modifiers.strength)
+    }
+
+    pub fn effective_agility(&self) -> u32 {
+        modify(self.agility, self.modifiers.agility)
+    }
+
+    pub fn effective_perception(&self) -> u32 {
+        modify(self.perception, self.modifiers.perception)
+    }
+
+    // The most that can be carried, in grams, before being overloaded
+    pub fn carry_capacity(&self) -> u32 {
+        self.effective_strength() * 2000
+    }
+
+    // The heaviest single thing that can be lifted, in grams
+    pub fn grab_limit(&self) -> u32 {
+        self.effective_strength() * 1000
+    }
+
+    // How many tiles something of the given weight can be thrown
+    pub fn throw_range(&self, grams: u32) -> u32 {
+        let reach = (self.effective_strength() + self.effective_agility()) / 4 + 2;
+        let drag = grams / (self.effective_strength() * 50);
+
+        reach.saturating_sub(drag).max(1)
+    }
+
+    // How much louder (or quieter) noises seem
+    pub fn hearing_bonus(&self) -> i32 {
+        (self.effective_perception() as i32 - AVERAGE as i32) / 2
+    }
+}
@@ -1,7 +1,7 @@
 use bracket_lib::prelude::RandomNumberGenerator;
 use vector::Vector;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Direction {
     North,
     East,
@@ -48,6 +48,61 @@ impl Direction {
         })
     }
 
+    // The direction nearest to pointing along an offset, if the offset goes anywhere
+    pub fn towards(offset: Vector<i32>) -> Option<Self> {
+        if offset == Vector(0, 0) {
+            return None;
+        }
+
+        // Angles run clockwise from east, as the y axis points south
+        let angle = (offset.1 as f32).atan2(offset.0 as f32).to_degrees();
+        let octant = ((angle + 360.0 + 22.5) / 45.0) as i32 % 8;
+
+        Some(match octant {
+            0 => Direction::East,
+            1 => Direction::SouthEast,
+            2 => Direction::South,
+            3 => Direction::SouthWest,
+            4 => Direction::West,
+            5 => Direction::NorthWest,
+            6 => Direction::North,
+            _ => Direction::NorthEast,
+        })
+    }
+
+    // The direction a number of eighth turns clockwise from this one
+    pub fn rotated(&self, turns: i32) -> Self {
+        let clockwise = [
+            Direction::North,
+            Direction::NorthEast,
+            Direction::East,
+            Direction::SouthEast,
+            Direction::South,
+            Direction::SouthWest,
+            Direction::West,
+            Direction::NorthWest,
+        ];
+        let index = clockwise
+            .iter()
+            .position(|direction| direction == self)
+            .unwrap() as i32;
+
+        clockwise[(index + turns).rem_euclid(8) as usize]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Direction::North => "north",
+            Direction::East => "east",
+            Direction::South => "south",
+            Direction::West => "west",
+            Direction::NorthWest => "northwest",
+            Direction::NorthEast => "northeast",
+            Direction::SouthWest => "southwest",
+            Direction::SouthEast => "southeast",
+        }
+    }
+
     pub fn all() -> [Direction; 8] {
         [
             Direction::North,
@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+// A door blocks movement and sight while it is closed. Locked doors have to be picked
+// open by hand, though the mechanisms they are linked to still work them
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub struct Door {
+    pub open: bool,
+    #[serde(default)]
+    pub locked: bool,
+    pub open_character: char,
+    pub closed_character: char,
+}
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+// Something that breaks once it has taken enough damage, leaving the named floor behind if any
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Durability {
+    pub hit_points: u32,
+    pub remains: Option<String>,
+}
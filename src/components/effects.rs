@@ -1,12 +1,16 @@
 use components::glyph::Colour;
-use serde::Deserialize;
+use components::status_effects::Status;
+use serde::{Deserialize, Serialize};
 
-#[derive(Copy, Clone, Debug, Deserialize)]
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
 pub enum Effect {
     Stain { colour: Colour },
+    Afflict { status: Status, turns: u32 },
+    Nourish { amount: u32 },
+    Quench { amount: u32 },
 }
 
-#[derive(Debug, Default, Deserialize, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
 pub struct Effects {
     pub touch: Vec<Effect>,
     pub consume: Vec<Effect>,
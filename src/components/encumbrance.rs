@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+// How weighed down something is by what it carries
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Encumbrance {
+    #[default]
+    Unburdened,
+    Burdened,
+    Strained,
+    Overloaded,
+}
+
+impl Encumbrance {
+    // Worked out in u64, so that no load is heavy enough to overflow
+    pub fn from_load(grams: u32, capacity: u32) -> Self {
+        let capacity = capacity as u64;
+
+        match grams as u64 * 4 {
+            load if load <= capacity * 2 => Encumbrance::Unburdened,
+            load if load <= capacity * 3 => Encumbrance::Burdened,
+            load if load <= capacity * 4 => Encumbrance::Strained,
+            _ => Encumbrance::Overloaded,
+        }
+    }
+
+    // Tiles taken off the distance things can be thrown
+    pub fn throw_penalty(&self) -> u32 {
+        match self {
+            Encumbrance::Unburdened => 0,
+            Encumbrance::Burdened => 1,
+            Encumbrance::Strained => 2,
+            Encumbrance::Overloaded => 4,
+        }
+    }
+
+    // The chance of a step being lost to the load. Even the overloaded can stagger on, so that
+    // nobody is left stuck in place
+    pub fn stumble_chance(&self) -> f32 {
+        match self {
+            Encumbrance::Unburdened | Encumbrance::Burdened => 0.0,
+            Encumbrance::Strained => 0.25,
+            Encumbrance::Overloaded => 0.5,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encumbrance::Unburdened => "unburdened",
+            Encumbrance::Burdened => "burdened",
+            Encumbrance::Strained => "strained",
+            Encumbrance::Overloaded => "overloaded",
+        }
+    }
+}
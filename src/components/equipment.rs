@@ -1,7 +1,7 @@
 use legion::Entity;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Deserialize, Default, Clone)]
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
 pub struct Equipment {
     pub held: Option<Entity>,
     pub storage: Option<Entity>,
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+
+// Experience points earned, and the levels and attribute points they have brought
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub struct Experience {
+    pub level: u32,
+    pub points: u32,
+    pub unspent: u32,    // Attribute points from levels gained, waiting to be chosen
+    pub discovered: u32, // Tiles seen for the first time, towards the next point for exploring
+}
+
+impl Default for Experience {
+    fn default() -> Self {
+        Self {
+            level: 1,
+            points: 0,
+            unspent: 0,
+            discovered: 0,
+        }
+    }
+}
+
+impl Experience {
+    // Points needed to reach the next level
+    pub fn next_level(&self) -> u32 {
+        self.level * 50
+    }
+
+    // Earn points, returning how many levels they brought
+    pub fn gain(&mut self, points: u32) -> u32 {
+        let mut levels = 0;
+
+        self.points += points;
+
+        while self.points >= self.next_level() {
+            self.points -= self.next_level();
+            self.level += 1;
+            self.unspent += 1;
+            levels += 1;
+        }
+
+        levels
+    }
+}
@@ -0,0 +1,7 @@
+use serde::{Deserialize, Serialize};
+
+// Flames that feed on whatever burns beneath them, dying down once nothing is left
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub struct Fire {
+    pub intensity: u32,
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+// A cloud that drifts outwards and thins away, hiding what lies behind it while thick enough
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Gas {
+    #[serde(default)]
+    pub kind: String, // The hazard this is, filled in as hazards are loaded
+    pub density: u32,
+    #[serde(default)]
+    pub dissipation: f32, // Chance each turn of losing a unit of density
+}
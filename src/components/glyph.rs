@@ -1,8 +1,8 @@
 use bracket_lib::prelude::{RGB, RGBA};
 use serde::de::Visitor;
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-#[derive(Debug, Deserialize, Default, Copy, Clone)]
+#[derive(Debug, Serialize, Deserialize, Default, Copy, Clone)]
 pub struct Glyph {
     pub character: char,
     pub colour: Colour,
@@ -46,3 +46,21 @@ impl<'de> Deserialize<'de> for Colour {
         Ok(deserializer.deserialize_str(ColourVisitor)?)
     }
 }
+
+// Colours are written as they are read, as hex strings (with alpha)
+impl Serialize for Colour {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let channel = |value: f32| (value * 255.0).round() as u8;
+
+        serializer.serialize_str(&format!(
+            "#{:02x}{:02x}{:02x}{:02x}",
+            channel(self.rgba.r),
+            channel(self.rgba.g),
+            channel(self.rgba.b),
+            channel(self.rgba.a)
+        ))
+    }
+}
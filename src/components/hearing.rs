@@ -0,0 +1,32 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use vector::Vector;
+
+// A noise as it reached a listener
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Heard {
+    pub position: Vector<i32>,
+    pub loudness: u32,
+    pub description: String,
+    pub turn: u64,
+}
+
+// Something that can hear, holding the noises it has heard and not yet acted on
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Hearing {
+    #[serde(default)]
+    pub heard: Vec<Heard>,
+}
+
+impl Hearing {
+    // The most noises kept, with the oldest forgotten first
+    pub const CAPACITY: usize = 8;
+
+    // Take the noises heard so far, loudest first
+    pub fn drain(&mut self) -> Vec<Heard> {
+        let mut heard = std::mem::take(&mut self.heard);
+        heard.sort_by_key(|heard| Reverse(heard.loudness));
+
+        heard
+    }
+}
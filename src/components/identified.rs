@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+// The item templates an entity has learnt to recognise, whatever they look like
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Identified {
+    pub templates: Vec<String>,
+}
+
+impl Identified {
+    pub fn knows(&self, template: &str) -> bool {
+        self.templates.iter().any(|known| known == template)
+    }
+
+    // Learn a template, returning whether it was new
+    pub fn learn(&mut self, template: &str) -> bool {
+        if self.knows(template) {
+            return false;
+        }
+
+        self.templates.push(String::from(template));
+        true
+    }
+}
@@ -0,0 +1,5 @@
+use serde::{Deserialize, Serialize};
+
+// Sets alight anything flammable it is left lying on
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone)]
+pub struct Ignites {}
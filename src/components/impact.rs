@@ -0,0 +1,8 @@
+use legion::Entity;
+
+// Given to something that has been thrown into an entity that stopped it
+#[derive(Debug, Copy, Clone)]
+pub struct Impact {
+    pub entity: Entity,
+    pub thrower: Option<Entity>,
+}
@@ -0,0 +1,5 @@
+use serde::{Deserialize, Serialize};
+
+// Marks something thrown that has just come to rest
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone)]
+pub struct Landed {}
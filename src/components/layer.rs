@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+// The layer an object occupies within its map stack, from the bottom up
+#[derive(
+    Serialize, Deserialize, Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub enum Layer {
+    Terrain,
+    Surface,
+    #[default]
+    Feature,
+    Item,
+    Creature,
+    Effect,
+}
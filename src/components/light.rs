@@ -1,7 +1,7 @@
 use components::glyph::Colour;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Debug, Default, Copy, Clone)]
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone)]
 pub struct Light {
     pub colour: Colour,
     pub intensity: f32,
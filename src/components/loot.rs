@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+// One of the items a loot table can give
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct LootChoice {
+    pub name: String,
+    pub probability: f32,
+    #[serde(default = "LootChoice::default_most")]
+    pub most: u32, // The most of the item a single roll can give
+}
+
+impl LootChoice {
+    fn default_most() -> u32 {
+        1
+    }
+}
+
+// What a container is filled with once it has been spawned. Each roll picks a choice at random,
+// which is kept with its probability
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Loot {
+    pub rolls: u32,
+    pub choices: Vec<LootChoice>,
+}
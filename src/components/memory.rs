@@ -1,6 +1,170 @@
+use clock::Clock;
+use components::glyph::Glyph;
+use std::sync::{Arc, RwLock};
 use vector::Vector;
 
-#[derive(Default, Clone, Debug)]
+// Turns after which a remembered item is no longer trusted to still be where it was seen
+pub const ITEM_STALE_TURNS: u64 = 300;
+
+// What was last seen on a tile
+#[derive(Debug, Copy, Clone)]
+pub struct Recollection {
+    pub terrain: Glyph,
+    pub item: Option<Glyph>,
+    pub turn: u64,
+}
+
+impl Recollection {
+    // The glyph to draw for this recollection, forgetting items that have gone stale
+    pub fn glyph(&self, clock: &Clock) -> Glyph {
+        match self.item {
+            Some(item) if clock.since(self.turn) < ITEM_STALE_TURNS => item,
+            _ => self.terrain,
+        }
+    }
+}
+
+// A grid of recollections covering the map
+#[derive(Debug)]
+pub struct SpatialMemory {
+    tiles: Vec<Option<Recollection>>,
+    size: Vector<i32>,
+}
+
+impl SpatialMemory {
+    pub fn new(size: Vector<i32>) -> Self {
+        Self {
+            tiles: vec![None; (size.0 * size.1) as usize],
+            size,
+        }
+    }
+
+    fn index(&self, position: Vector<i32>) -> Option<usize> {
+        if position.0 >= 0
+            && position.1 >= 0
+            && position.0 < self.size.0
+            && position.1 < self.size.1
+        {
+            Some((position.0 + position.1 * self.size.0) as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, position: Vector<i32>) -> Option<&Recollection> {
+        self.index(position)
+            .and_then(|index| self.tiles[index].as_ref())
+    }
+
+    pub fn remember(&mut self, position: Vector<i32>, recollection: Recollection) {
+        if let Some(index) = self.index(position) {
+            self.tiles[index] = Some(recollection);
+        }
+    }
+
+    // Take in whatever another memory recalls more recently than this one does
+    pub fn merge(&mut self, other: &SpatialMemory) {
+        for (tile, theirs) in self.tiles.iter_mut().zip(other.tiles.iter()) {
+            if let Some(theirs) = theirs {
+                if tile.is_none_or(|ours| ours.turn < theirs.turn) {
+                    *tile = Some(*theirs);
+                }
+            }
+        }
+    }
+}
+
+// Spatial memory (Previously seen tiles). Allies can share what they have seen
+#[derive(Clone, Debug)]
 pub struct Memory {
-    pub(crate) spatial: Vec<Vector<i32>>, // Spatial memory (Previously seen locations
+    pub spatial: Arc<RwLock<SpatialMemory>>,
+}
+
+impl Memory {
+    pub fn new(size: Vector<i32>) -> Self {
+        Self {
+            spatial: Arc::new(RwLock::new(SpatialMemory::new(size))),
+        }
+    }
+
+    // Pool what two allies remember, so that each recalls the latest of what either has seen
+    pub fn share(&self, ally: &Memory) {
+        if Arc::ptr_eq(&self.spatial, &ally.spatial) {
+            return;
+        }
+
+        let mut ours = self.spatial.write().unwrap();
+        let mut theirs = ally.spatial.write().unwrap();
+
+        ours.merge(&theirs);
+        theirs.merge(&ours);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seen(character: char, turn: u64) -> Recollection {
+        Recollection {
+            terrain: Glyph {
+                character,
+                ..Glyph::default()
+            },
+            item: None,
+            turn,
+        }
+    }
+
+    fn recalled(memory: &Memory, position: Vector<i32>) -> Option<(char, u64)> {
+        memory
+            .spatial
+            .read()
+            .unwrap()
+            .get(position)
+            .map(|recollection| (recollection.terrain.character, recollection.turn))
+    }
+
+    #[test]
+    fn sharing_keeps_the_most_recent_recollection() {
+        let size = Vector(4, 4);
+        let (ours, theirs) = (Memory::new(size), Memory::new(size));
+
+        ours.spatial
+            .write()
+            .unwrap()
+            .remember(Vector(0, 0), seen('#', 5));
+        ours.spatial
+            .write()
+            .unwrap()
+            .remember(Vector(1, 0), seen('.', 9));
+        theirs
+            .spatial
+            .write()
+            .unwrap()
+            .remember(Vector(0, 0), seen('+', 7));
+        theirs
+            .spatial
+            .write()
+            .unwrap()
+            .remember(Vector(1, 0), seen('#', 3));
+        theirs
+            .spatial
+            .write()
+            .unwrap()
+            .remember(Vector(2, 0), seen('~', 1));
+
+        ours.share(&theirs);
+
+        for memory in [&ours, &theirs].iter() {
+            assert_eq!(recalled(memory, Vector(0, 0)), Some(('+', 7)));
+            assert_eq!(recalled(memory, Vector(1, 0)), Some(('.', 9)));
+            assert_eq!(recalled(memory, Vector(2, 0)), Some(('~', 1)));
+            assert_eq!(recalled(memory, Vector(3, 0)), None);
+        }
+
+        // Sharing with itself changes nothing, rather than waiting on its own lock
+        ours.share(&ours.clone());
+        assert_eq!(recalled(&ours, Vector(0, 0)), Some(('+', 7)));
+    }
 }
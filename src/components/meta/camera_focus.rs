@@ -1,2 +1,8 @@
-#[derive(Default)]
-pub struct CameraFocus;
+use legion::Entity;
+
+// Marks the entity the camera follows. Focus lent to another entity (such as a thrown item)
+// is handed back once that entity comes to rest
+#[derive(Default, Copy, Clone)]
+pub struct CameraFocus {
+    pub returns_to: Option<Entity>,
+}
@@ -1,21 +1,51 @@
 pub mod actions;
+pub mod attributes;
 pub mod barrier;
 pub mod collision;
 pub mod description;
 pub mod direction;
 pub mod displacement;
+pub mod door;
+pub mod durability;
 pub mod effects;
+pub mod encumbrance;
 pub mod equipment;
+pub mod experience;
+pub mod fire;
+pub mod gas;
 pub mod glyph;
+pub mod hearing;
+pub mod identified;
+pub mod ignites;
 pub mod illumination;
+pub mod impact;
+pub mod landed;
+pub mod layer;
 pub mod light;
+pub mod loot;
 pub mod memory;
 pub mod meta;
+pub mod needs;
+pub mod noise;
 pub mod opaque;
+pub mod perishable;
 pub mod position;
+pub mod quantity;
+pub mod skills;
 pub mod spawn;
+pub mod stained;
+pub mod status_effects;
+pub mod stepped;
 pub mod storage;
+pub mod surface;
+pub mod tags;
 pub mod target;
+pub mod template;
 pub mod tile;
+pub mod tracks;
+pub mod trader;
+pub mod trigger;
+pub mod unidentified;
+pub mod value;
 pub mod velocity;
 pub mod weight;
@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+
+// How much food and water can be taken in before being full, in turns' worth
+pub const MAX_NUTRITION: u32 = 1500;
+pub const MAX_HYDRATION: u32 = 1000;
+
+// How badly a need is felt
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stage {
+    Sated,
+    Low,
+    Weak,
+    Desperate,
+}
+
+impl Stage {
+    pub fn of(value: u32, maximum: u32) -> Self {
+        match value * 100 / maximum {
+            50.. => Stage::Sated,
+            25.. => Stage::Low,
+            10.. => Stage::Weak,
+            _ => Stage::Desperate,
+        }
+    }
+}
+
+// Food and water, both of which run down with every turn
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub struct Needs {
+    pub nutrition: u32,
+    pub hydration: u32,
+}
+
+impl Default for Needs {
+    fn default() -> Self {
+        Self {
+            nutrition: MAX_NUTRITION,
+            hydration: MAX_HYDRATION,
+        }
+    }
+}
+
+impl Needs {
+    pub fn hunger(&self) -> Stage {
+        Stage::of(self.nutrition, MAX_NUTRITION)
+    }
+
+    pub fn thirst(&self) -> Stage {
+        Stage::of(self.hydration, MAX_HYDRATION)
+    }
+
+    pub fn nourish(&mut self, amount: u32) {
+        self.nutrition = (self.nutrition + amount).min(MAX_NUTRITION);
+    }
+
+    pub fn quench(&mut self, amount: u32) {
+        self.hydration = (self.hydration + amount).min(MAX_HYDRATION);
+    }
+
+    pub fn hunger_name(&self) -> Option<&'static str> {
+        match self.hunger() {
+            Stage::Sated => None,
+            Stage::Low => Some("hungry"),
+            Stage::Weak => Some("weak with hunger"),
+            Stage::Desperate => Some("starving"),
+        }
+    }
+
+    pub fn thirst_name(&self) -> Option<&'static str> {
+        match self.thirst() {
+            Stage::Sated => None,
+            Stage::Low => Some("thirsty"),
+            Stage::Weak => Some("parched"),
+            Stage::Desperate => Some("dehydrated"),
+        }
+    }
+}
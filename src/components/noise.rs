@@ -0,0 +1,11 @@
+use legion::Entity;
+use vector::Vector;
+
+// A sound made at a position, carried as far as its volume allows
+#[derive(Debug, Clone)]
+pub struct Noise {
+    pub position: Vector<i32>,
+    pub volume: u32,
+    pub description: String,
+    pub source: Option<Entity>, // Whatever made the noise, which does not hear itself
+}
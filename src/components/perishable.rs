@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+// Food that rots once it has been left long enough
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub struct Perishable {
+    pub turns: u32,
+    #[serde(default)]
+    pub spoiled: bool,
+}
@@ -1,6 +1,7 @@
 use crate::vector::Vector;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Hash, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Position {
     pub vector: Vector<i32>,
 }
@@ -0,0 +1,85 @@
+use components::description::Description;
+use components::glyph::{Colour, Glyph};
+use legion::*;
+use serde::{Deserialize, Serialize};
+
+// How many identical items a single entity stands for. Only items with a quantity stack
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub struct Quantity {
+    pub count: u32,
+}
+
+impl Default for Quantity {
+    fn default() -> Self {
+        Self { count: 1 }
+    }
+}
+
+impl Quantity {
+    // A name along with how many there are, if there is more than one
+    pub fn label(&self, name: &str) -> String {
+        if self.count > 1 {
+            format!("{} x{}", name, self.count)
+        } else {
+            String::from(name)
+        }
+    }
+}
+
+// How many items an entity stands for
+pub fn count<W: EntityStore>(world: &W, entity: Entity) -> u32 {
+    world
+        .entry_ref(entity)
+        .ok()
+        .and_then(|entry| {
+            entry
+                .get_component::<Quantity>()
+                .ok()
+                .map(|quantity| quantity.count)
+        })
+        .unwrap_or(1)
+}
+
+// An entity's name, along with how many it stands for if there is more than one
+pub fn label<W: EntityStore>(world: &W, entity: Entity, name: &str) -> String {
+    Quantity {
+        count: count(world, entity),
+    }
+    .label(name)
+}
+
+// An entity's name and colour, along with how many it stands for, ready to go into a label
+pub fn named<W: EntityStore>(world: &W, entity: Entity) -> (String, Colour) {
+    let entry = world.entry_ref(entity).ok();
+    let description = entry
+        .as_ref()
+        .and_then(|entry| entry.get_component::<Description>().ok().cloned())
+        .unwrap_or_default();
+    let glyph = entry
+        .as_ref()
+        .and_then(|entry| entry.get_component::<Glyph>().ok().copied())
+        .unwrap_or_default();
+
+    (label(world, entity, &description.name), glyph.colour)
+}
+
+// The indefinite article to put before a single item's name
+pub fn article(name: &str) -> &'static str {
+    match name.chars().next().map(|c| c.to_ascii_lowercase()) {
+        Some('a') | Some('e') | Some('i') | Some('o') | Some('u') => "an",
+        _ => "a",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_starting_with_a_vowel_take_an() {
+        assert_eq!(article("amulet"), "an");
+        assert_eq!(article("Iron sword"), "an");
+        assert_eq!(article("ring"), "a");
+        assert_eq!(article(""), "a");
+    }
+}
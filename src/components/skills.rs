@@ -0,0 +1,39 @@
+use serde::{Deserialize, Serialize};
+
+// A skill that improves with practice
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone)]
+pub struct Skill {
+    pub level: u32,
+    pub practice: u32,
+}
+
+impl Skill {
+    // Practice needed to reach the next level
+    pub fn next_level(&self) -> u32 {
+        (self.level + 1) * 10
+    }
+
+    // Put the skill to use, returning whether it improved
+    pub fn practise(&mut self, amount: u32) -> bool {
+        self.practice += amount;
+
+        if self.practice >= self.next_level() {
+            self.practice -= self.next_level();
+            self.level += 1;
+
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone)]
+pub struct Skills {
+    #[serde(default)]
+    pub throwing: Skill,
+    #[serde(default)]
+    pub stealth: Skill,
+    #[serde(default)]
+    pub lockpicking: Skill,
+}
@@ -1,21 +1,22 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum SpawnType {
     Floor,
     Wall,
+    Feature,
     Item,
     Creature,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct SpawnDescription {
     pub ty: SpawnType,
     pub name: String,
     pub probability: f32,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Spawn {
     pub choices: Vec<SpawnDescription>,
 }
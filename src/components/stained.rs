@@ -0,0 +1,10 @@
+use components::glyph::Colour;
+use serde::{Deserialize, Serialize};
+
+// A stain fading from a glyph, which is back to its original colour once the stain is gone
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub struct Stained {
+    pub original: Colour,
+    pub colour: Colour,
+    pub turns: u32,
+}
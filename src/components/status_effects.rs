@@ -0,0 +1,191 @@
+use components::attributes::Modifiers;
+use components::glyph::Colour;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    Poisoned,
+    Burning,
+    Blinded,
+    Slowed,
+    Hasted,
+    Glowing,
+}
+
+// What happens when a status is suffered again while it is still running
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Stacking {
+    Refresh,   // The longer of the two durations is kept
+    Extend,    // The durations add up
+    Intensify, // The duration is refreshed and the magnitudes add up
+}
+
+// The strongest any status can grow by stacking
+const MAX_MAGNITUDE: u32 = 5;
+
+// How far the blinded can see
+const BLIND_RADIUS: i32 = 1;
+
+impl Status {
+    pub fn stacking(&self) -> Stacking {
+        match self {
+            Status::Poisoned => Stacking::Intensify,
+            Status::Blinded | Status::Glowing => Stacking::Extend,
+            Status::Burning | Status::Slowed | Status::Hasted => Stacking::Refresh,
+        }
+    }
+
+    // Statuses that cannot be suffered at the same time as this one
+    pub fn cancels(&self) -> Option<Status> {
+        match self {
+            Status::Slowed => Some(Status::Hasted),
+            Status::Hasted => Some(Status::Slowed),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Status::Poisoned => "poisoned",
+            Status::Burning => "burning",
+            Status::Blinded => "blinded",
+            Status::Slowed => "slowed",
+            Status::Hasted => "hasted",
+            Status::Glowing => "glowing",
+        }
+    }
+
+    pub fn colour(&self) -> Colour {
+        match self {
+            Status::Poisoned => (120, 200, 60, 255),
+            Status::Burning => (255, 120, 30, 255),
+            Status::Blinded => (140, 140, 140, 255),
+            Status::Slowed => (90, 120, 200, 255),
+            Status::Hasted => (240, 220, 90, 255),
+            Status::Glowing => (200, 240, 255, 255),
+        }
+        .into()
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub struct StatusEffect {
+    pub status: Status,
+    pub turns: u32,
+    #[serde(default = "StatusEffect::default_magnitude")]
+    pub magnitude: u32,
+}
+
+impl StatusEffect {
+    fn default_magnitude() -> u32 {
+        1
+    }
+}
+
+// The timed conditions an entity is suffering
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct StatusEffects {
+    #[serde(default)]
+    pub effects: Vec<StatusEffect>,
+    #[serde(default)]
+    pub reported: Vec<Status>, // What the sufferer has already been told about
+    #[serde(default)]
+    pub lit: bool, // Whether the light the sufferer gives off is down to its statuses
+}
+
+impl StatusEffects {
+    pub fn get(&self, status: Status) -> Option<&StatusEffect> {
+        self.effects.iter().find(|effect| effect.status == status)
+    }
+
+    pub fn has(&self, status: Status) -> bool {
+        self.get(status).is_some()
+    }
+
+    // Suffer a status, stacking it with any already running
+    pub fn afflict(&mut self, effect: StatusEffect) {
+        if let Some(cancelled) = effect.status.cancels() {
+            self.cure(cancelled);
+        }
+
+        match self
+            .effects
+            .iter_mut()
+            .find(|existing| existing.status == effect.status)
+        {
+            Some(existing) => match effect.status.stacking() {
+                Stacking::Refresh => existing.turns = existing.turns.max(effect.turns),
+                Stacking::Extend => existing.turns += effect.turns,
+                Stacking::Intensify => {
+                    existing.turns = existing.turns.max(effect.turns);
+                    existing.magnitude = (existing.magnitude + effect.magnitude).min(MAX_MAGNITUDE);
+                }
+            },
+            None => self.effects.push(effect),
+        }
+    }
+
+    pub fn cure(&mut self, status: Status) {
+        self.effects.retain(|effect| effect.status != status);
+    }
+
+    // Let a turn pass, returning the statuses that wore off
+    pub fn tick(&mut self) -> Vec<Status> {
+        for effect in self.effects.iter_mut() {
+            effect.turns = effect.turns.saturating_sub(1);
+        }
+
+        let expired = self
+            .effects
+            .iter()
+            .filter(|effect| effect.turns == 0)
+            .map(|effect| effect.status)
+            .collect();
+
+        self.effects.retain(|effect| effect.turns > 0);
+
+        expired
+    }
+
+    // How far can be seen, given how far could be seen unhindered
+    pub fn sight(&self, radius: i32) -> i32 {
+        if self.has(Status::Blinded) {
+            radius.min(BLIND_RADIUS)
+        } else {
+            radius
+        }
+    }
+
+    // How long an action takes, in half turns
+    pub fn action_cost(&self) -> u32 {
+        if self.has(Status::Hasted) {
+            1
+        } else if self.has(Status::Slowed) {
+            4
+        } else {
+            2
+        }
+    }
+
+    // How the statuses change the sufferer's attributes
+    pub fn modifiers(&self) -> Modifiers {
+        let mut modifiers = Modifiers::default();
+
+        for effect in &self.effects {
+            match effect.status {
+                Status::Poisoned => modifiers.strength -= effect.magnitude as i32,
+                Status::Slowed => modifiers.agility -= 2,
+                Status::Hasted => modifiers.agility += 2,
+                _ => {}
+            }
+        }
+
+        modifiers
+    }
+
+    // Whether the statuses make the sufferer give off light
+    pub fn shines(&self) -> bool {
+        self.has(Status::Glowing) || self.has(Status::Burning)
+    }
+}
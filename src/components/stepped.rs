@@ -0,0 +1,7 @@
+use vector::Vector;
+
+// Marks something that has just stepped from one position to another
+#[derive(Debug, Copy, Clone)]
+pub struct Stepped {
+    pub from: Vector<i32>,
+}
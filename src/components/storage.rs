@@ -1,5 +1,20 @@
-use legion::Entity;
+use legion::{Entity, EntityStore, IntoQuery};
+use serde::{Deserialize, Serialize};
 
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct Storage {
     pub objects: Vec<Entity>,
 }
+
+// Whether an entity is a container itself or lies anywhere within it, however deeply nested
+pub fn encloses<W: EntityStore>(world: &W, container: Entity, entity: Entity) -> bool {
+    container == entity
+        || <&Storage>::query()
+            .get(world, container)
+            .is_ok_and(|storage| {
+                storage
+                    .objects
+                    .iter()
+                    .any(|object| encloses(world, *object, entity))
+            })
+}
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+// A layer of liquid or growth covering a floor
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Surface {
+    #[serde(default)]
+    pub kind: String, // The coating this is, filled in as coatings are loaded
+    pub volume: u32, // How much there is, gone once it reaches zero
+    #[serde(default)]
+    pub spreads: bool, // Flows onto neighbouring floors while there is more than a little
+    #[serde(default)]
+    pub evaporation: f32, // Chance each turn of losing a unit of volume
+    #[serde(default)]
+    pub growth: f32, // Chance each turn of creeping onto a neighbouring floor
+    #[serde(default)]
+    pub tracked: bool, // Carried onto the floors walked onto afterwards
+    #[serde(default)]
+    pub slippery: bool, // Whatever moves onto it slides a step further
+    #[serde(default)]
+    pub drag: f32, // Chance of a step out of it being lost
+    #[serde(default)]
+    pub flammable: bool,
+    #[serde(default)]
+    pub extinguishes: bool,
+}
@@ -0,0 +1,36 @@
+use legion::*;
+use serde::{Deserialize, Serialize};
+
+// The tags that systems act on
+pub const FRAGILE: &str = "fragile";
+pub const FLAMMABLE: &str = "flammable";
+pub const EDIBLE: &str = "edible";
+pub const LUMINOUS: &str = "luminous";
+pub const CONTAINER: &str = "container";
+pub const CURRENCY: &str = "currency";
+
+// Loose categories an entity belongs to, such as "gem" or "sharp"
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+#[serde(transparent)]
+pub struct Tags(pub Vec<String>);
+
+impl Tags {
+    pub fn has(&self, tag: &str) -> bool {
+        self.0.iter().any(|own| own == tag)
+    }
+
+    pub fn insert(&mut self, tag: &str) {
+        if !self.has(tag) {
+            self.0.push(String::from(tag));
+        }
+    }
+}
+
+// Whether an entity has a tag
+pub fn tagged<W: EntityStore>(world: &W, entity: Entity, tag: &str) -> bool {
+    world.entry_ref(entity).is_ok_and(|entry| {
+        entry
+            .get_component::<Tags>()
+            .is_ok_and(|tags| tags.has(tag))
+    })
+}
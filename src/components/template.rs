@@ -0,0 +1,7 @@
+use serde::{Deserialize, Serialize};
+
+// The name of the asset template an entity was spawned from
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+pub struct Template {
+    pub name: String,
+}
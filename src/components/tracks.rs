@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+
+// A coating stuck to something that moves, left behind a little at each step
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Tracks {
+    pub kind: String,
+    pub steps: u32,
+}
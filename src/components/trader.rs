@@ -0,0 +1,5 @@
+use serde::{Deserialize, Serialize};
+
+// A creature that buys and sells, selling from what it has in storage
+#[derive(Serialize, Deserialize, Debug, Default, Copy, Clone)]
+pub struct Trader {}
@@ -0,0 +1,18 @@
+use legion::Entity;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TriggerKind {
+    Lever,         // Toggles its targets when operated
+    PressurePlate, // Holds its targets open while anything is on it
+}
+
+// Works the doors it is linked to. Links are made when the dungeon is furnished
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Trigger {
+    pub kind: TriggerKind,
+    #[serde(default)]
+    pub active: bool,
+    #[serde(default)]
+    pub targets: Vec<Entity>,
+}
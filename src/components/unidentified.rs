@@ -0,0 +1,15 @@
+use components::description::Description;
+use components::glyph::Colour;
+use serde::{Deserialize, Serialize};
+
+// An item whose kind is hidden behind a random appearance until it is identified
+#[derive(Serialize, Deserialize, Debug, Default, Clone)]
+pub struct Unidentified {
+    pub kind: String, // The sort of appearance it takes, such as "potion"
+    #[serde(default)]
+    pub description: Description, // What the item really is, shown once it is identified
+    #[serde(default)]
+    pub colour: Colour, // Its true colour, restored once it is identified
+    #[serde(default)]
+    pub examined: bool, // Whether it has already been studied, which only ever helps once
+}
@@ -0,0 +1,95 @@
+use components::equipment::Equipment;
+use components::perishable::Perishable;
+use components::quantity;
+use components::stained::Stained;
+use components::storage::Storage;
+use components::tags::{tagged, CURRENCY};
+use components::unidentified::Unidentified;
+use legion::*;
+use serde::{Deserialize, Serialize};
+
+// The share of its worth a trader pays for something, rather than asks for it
+const SELLING_SHARE: f32 = 0.5;
+
+// Traders pay little for what the seller cannot even name
+const UNIDENTIFIED_SHARE: f32 = 0.25;
+
+// How much of its worth something keeps once it is rotten or stained
+const SPOILED_SHARE: f32 = 0.1;
+const STAINED_SHARE: f32 = 0.75;
+
+// What a single item is worth in coins
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+pub struct Value {
+    pub coins: u32,
+}
+
+// What an entity is worth in its present condition, counting every item in a stack. Anything
+// without a value cannot be traded
+fn worth<W: EntityStore>(world: &W, entity: Entity, share: f32) -> Option<u32> {
+    let entry = world.entry_ref(entity).ok()?;
+    let value = entry.get_component::<Value>().ok()?;
+    let mut share = share;
+
+    if entry
+        .get_component::<Perishable>()
+        .is_ok_and(|perishable| perishable.spoiled)
+    {
+        share *= SPOILED_SHARE;
+    }
+
+    if entry.get_component::<Stained>().is_ok() {
+        share *= STAINED_SHARE;
+    }
+
+    let coins = value.coins * quantity::count(world, entity);
+
+    Some(((coins as f32 * share).round() as u32).max(coins.min(1)))
+}
+
+// What a trader asks for something
+pub fn asking_price<W: EntityStore>(world: &W, entity: Entity) -> Option<u32> {
+    worth(world, entity, 1.0)
+}
+
+// What a trader offers for something
+pub fn offer<W: EntityStore>(world: &W, entity: Entity) -> Option<u32> {
+    let unidentified = world
+        .entry_ref(entity)
+        .is_ok_and(|entry| entry.get_component::<Unidentified>().is_ok());
+
+    if unidentified {
+        worth(world, entity, SELLING_SHARE * UNIDENTIFIED_SHARE)
+    } else {
+        worth(world, entity, SELLING_SHARE)
+    }
+}
+
+// The stacks of coins held or stored
+pub fn purse<W: EntityStore>(world: &W, equipment: &Equipment) -> Vec<Entity> {
+    let stored = equipment
+        .storage
+        .and_then(|storage| world.entry_ref(storage).ok())
+        .and_then(|entry| {
+            entry
+                .get_component::<Storage>()
+                .ok()
+                .map(|storage| storage.objects.clone())
+        })
+        .unwrap_or_default();
+
+    equipment
+        .held
+        .into_iter()
+        .chain(stored)
+        .filter(|item| tagged(world, *item, CURRENCY))
+        .collect()
+}
+
+// How many coins are held or stored
+pub fn coins<W: EntityStore>(world: &W, equipment: &Equipment) -> u32 {
+    purse(world, equipment)
+        .into_iter()
+        .map(|stack| quantity::count(world, stack))
+        .sum()
+}
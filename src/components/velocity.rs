@@ -1,6 +1,9 @@
 use components::direction::Direction;
+use legion::Entity;
 
 pub struct Velocity {
     pub direction: Direction,
     pub magnitude: i32,
+    pub range: u32,              // Steps left before coming to rest
+    pub thrower: Option<Entity>, // Whoever threw it, to be credited with what it defeats
 }
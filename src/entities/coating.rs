@@ -0,0 +1,23 @@
+use serde::Deserialize;
+use std::sync::{Arc, RwLock};
+
+use components::description::*;
+use components::effects::*;
+use components::glyph::*;
+use components::position::*;
+use components::surface::*;
+
+use components::layer::Layer;
+use map::{Map, Object};
+use vector::Vector;
+
+use legion::systems::CommandBuffer;
+use object_derive::ObjectBase;
+
+#[derive(ObjectBase, Clone, Debug, Deserialize)]
+pub struct Coating {
+    pub glyph: Glyph,
+    pub description: Description,
+    pub surface: Surface,
+    pub effects: Option<Effects>,
+}
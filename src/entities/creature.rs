@@ -1,16 +1,24 @@
 use serde::Deserialize;
 use std::sync::{Arc, RwLock};
 
+use components::attributes::*;
+use components::barrier::*;
 use components::description::*;
+use components::durability::*;
 
 use components::glyph::*;
+use components::hearing::*;
 use components::position::*;
 use components::weight::*;
 
+use components::layer::Layer;
 use map::{Map, Object};
 use vector::Vector;
 
 use components::equipment::Equipment;
+use components::loot::Loot;
+use components::storage::Storage;
+use components::trader::Trader;
 
 use legion::systems::CommandBuffer;
 use object_derive::ObjectBase;
@@ -21,4 +29,11 @@ pub struct Creature {
     pub description: Description,
     pub weight: Weight,
     pub equipment: Option<Equipment>,
+    pub hearing: Option<Hearing>,
+    pub attributes: Option<Attributes>,
+    pub barrier: Option<Barrier>,
+    pub durability: Option<Durability>,
+    pub storage: Option<Storage>,
+    pub loot: Option<Loot>,
+    pub trader: Option<Trader>,
 }
@@ -0,0 +1,34 @@
+use serde::Deserialize;
+use std::sync::{Arc, RwLock};
+
+use components::glyph::*;
+use components::position::*;
+
+use components::barrier::*;
+use components::opaque::*;
+
+use components::layer::Layer;
+use map::{Map, Object};
+use vector::Vector;
+
+use components::description::Description;
+use components::door::Door;
+use components::light::Light;
+use components::loot::Loot;
+use components::storage::Storage;
+use components::trigger::Trigger;
+use legion::systems::CommandBuffer;
+use object_derive::ObjectBase;
+
+#[derive(ObjectBase, Clone, Debug, Deserialize)]
+pub struct Feature {
+    pub glyph: Glyph,
+    pub description: Description,
+    pub barrier: Option<Barrier>,
+    pub opaque: Option<Opaque>,
+    pub door: Option<Door>,
+    pub trigger: Option<Trigger>,
+    pub light: Option<Light>,
+    pub storage: Option<Storage>,
+    pub loot: Option<Loot>,
+}
@@ -5,6 +5,7 @@ use components::spawn::*;
 use components::tile::*;
 use legion::systems::CommandBuffer;
 
+use components::layer::Layer;
 use map::{Map, Object};
 use object_derive::ObjectBase;
 use serde::Deserialize;
@@ -0,0 +1,27 @@
+use serde::Deserialize;
+use std::sync::{Arc, RwLock};
+
+use components::description::*;
+use components::fire::*;
+use components::gas::*;
+use components::glyph::*;
+use components::opaque::*;
+use components::position::*;
+
+use components::layer::Layer;
+use map::{Map, Object};
+use vector::Vector;
+
+use components::light::Light;
+use legion::systems::CommandBuffer;
+use object_derive::ObjectBase;
+
+#[derive(ObjectBase, Clone, Debug, Deserialize)]
+pub struct Hazard {
+    pub glyph: Glyph,
+    pub description: Description,
+    pub fire: Option<Fire>,
+    pub gas: Option<Gas>,
+    pub opaque: Option<Opaque>,
+    pub light: Option<Light>,
+}
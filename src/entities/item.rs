@@ -1,12 +1,24 @@
 use serde::Deserialize;
+use std::mem;
 use std::sync::{Arc, RwLock};
 
 use components::description::*;
 use components::effects::*;
 use components::glyph::*;
+use components::ignites::*;
+use components::loot::*;
+use components::perishable::*;
 use components::position::*;
+use components::quantity::*;
+use components::storage::*;
+use components::tags::*;
+use components::template::*;
+use components::unidentified::*;
+use components::value::*;
 use components::weight::*;
 
+use appearance::Appearance;
+use components::layer::Layer;
 use map::{Map, Object};
 use vector::Vector;
 
@@ -21,4 +33,50 @@ pub struct Item {
     pub weight: Weight,
     pub effects: Option<Effects>,
     pub light: Option<Light>,
+    pub ignites: Option<Ignites>,
+    pub perishable: Option<Perishable>,
+    pub template: Option<Template>,
+    pub tags: Option<Tags>,
+    pub storage: Option<Storage>,
+    pub unidentified: Option<Unidentified>,
+    pub quantity: Option<Quantity>,
+    pub loot: Option<Loot>,
+    pub value: Option<Value>,
+}
+
+impl Item {
+    // Tag the item for whatever its components make it, so that systems need only ask its tags
+    pub fn infer_tags(&mut self) {
+        let tags = self.tags.get_or_insert_with(Tags::default);
+
+        if self.light.is_some() {
+            tags.insert(LUMINOUS);
+        }
+
+        if self.storage.is_some() {
+            tags.insert(CONTAINER);
+        }
+
+        if self
+            .effects
+            .as_ref()
+            .is_some_and(|effects| !effects.consume.is_empty())
+        {
+            tags.insert(EDIBLE);
+        }
+    }
+
+    // Hide the item behind an appearance, keeping what it really is for once it is identified
+    pub fn disguise(&mut self, appearance: &Appearance) {
+        if let Some(unidentified) = self.unidentified.as_mut() {
+            unidentified.description = mem::replace(
+                &mut self.description,
+                Description {
+                    name: appearance.name.clone(),
+                    description: appearance.description.clone(),
+                },
+            );
+            unidentified.colour = mem::replace(&mut self.glyph.colour, appearance.colour);
+        }
+    }
 }
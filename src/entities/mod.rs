@@ -1,4 +1,7 @@
+pub mod coating;
 pub mod creature;
+pub mod feature;
 pub mod floor;
+pub mod hazard;
 pub mod item;
 pub mod wall;
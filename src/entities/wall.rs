@@ -7,10 +7,12 @@ use components::position::*;
 use components::barrier::*;
 use components::opaque::*;
 
+use components::layer::Layer;
 use map::{Map, Object};
 use vector::Vector;
 
 use components::description::Description;
+use components::durability::Durability;
 use components::light::Light;
 use legion::systems::CommandBuffer;
 use object_derive::ObjectBase;
@@ -22,4 +24,5 @@ pub struct Wall {
     pub description: Description,
     pub opaque: Option<Opaque>,
     pub light: Option<Light>,
+    pub durability: Option<Durability>,
 }
@@ -1,41 +1,73 @@
+use std::collections::HashSet;
 use std::sync::{Arc, RwLock};
 
+use camera::{Camera, CameraMode};
+use clock::Clock;
 use components::position::Position;
-use WINDOW_DIMENSIONS;
-use {systems, WINDOW_CENTER};
+use systems::craft::at_hand;
+use systems::encumbrance::carried_weight;
+use systems::stack::split;
+use {systems, FOV_RADIUS};
+use {CHUNKS_DIRECTORY_PATH, WINDOW_DIMENSIONS};
 
 use bracket_lib::prelude::{
     field_of_view, main_loop, string_to_cp437, to_cp437, Algorithm2D, BTerm, BTermBuilder, BaseMap,
     GameState, Input, Point, RandomNumberGenerator, RgbaLerp, VirtualKeyCode, BLACK,
     BLANCHED_ALMOND, RGB, RGBA, WHITE_SMOKE,
 };
+use map::streaming::Streamer;
 use map::{Map, Object};
 
 use components::collision::Collision;
 use components::glyph::Glyph;
+use components::layer::Layer;
 
 use components::displacement::Displacement;
 use components::memory::Memory;
 
 use components::description::Description;
 use components::direction::Direction;
+use components::encumbrance::Encumbrance;
 use components::meta::camera_focus::CameraFocus;
 use components::tile::Tile;
-use interface::{Element, List};
+use interface::log::{Category, Log};
+use interface::menu::{Cursor, Dialog, Menu, Prompt, QuantityPicker};
+use interface::screen::Screen;
+use interface::{Element, Interactive, List, Response};
 use legion::*;
 use location::Dungeon;
 use std::ops::Deref;
-use GUI_WIDTH;
-use {FLOORS, WALLS};
+use {COATINGS, CREATURES, FEATURES, FLOORS, ITEMS, RECIPES, WALLS};
 
+use components::actions::buy::Buy;
+use components::actions::consume::Consume;
+use components::actions::craft::Craft;
+use components::actions::examine::Examine;
 use components::actions::grab::Grab;
+use components::actions::sell::Sell;
+use components::actions::stow::Stow;
+use components::actions::take::Take;
 use components::equipment::Equipment;
-use components::storage::Storage;
+use components::storage::{encloses, Storage};
+use components::weight::Weight;
 
 use components::actions::throw::Throw;
+use components::attributes::Attributes;
+use components::experience::Experience;
+use components::hearing::Hearing;
+use components::identified::Identified;
 use components::illumination::Illumination;
 use components::light::Light;
 use components::meta::intention::{Intent, Intention};
+use components::meta::player::Player;
+use components::needs::Needs;
+use components::quantity;
+use components::skills::Skills;
+use components::status_effects::StatusEffects;
+use components::tags::{tagged, CURRENCY};
+use components::trader::Trader;
+use components::unidentified::Unidentified;
+use components::value::{self, asking_price, offer};
 
 use interface::Label;
 use label;
@@ -43,10 +75,30 @@ use legion::systems::CommandBuffer;
 
 use vector::Vector;
 
-pub const EVENT_LOG_PANE_SIZE: (i32, i32) = (WINDOW_DIMENSIONS.0, WINDOW_DIMENSIONS.1 / 4);
-pub const EVENT_LOG_PANE_POSITION: (i32, i32) = (0, WINDOW_DIMENSIONS.1 - EVENT_LOG_PANE_SIZE.1);
-pub const INVENTORY_PANE_SIZE: (i32, i32) = (WINDOW_DIMENSIONS.0 / 4, WINDOW_DIMENSIONS.1);
-pub const INVENTORY_PANE_POSITION: (i32, i32) = (WINDOW_DIMENSIONS.0 - INVENTORY_PANE_SIZE.0, 0);
+pub const EVENT_LOG_CAPACITY: usize = 500;
+
+// The colour remembered tiles fade towards, and how far
+const REMEMBERED_COLOUR: (u8, u8, u8, u8) = (30, 30, 35, 255);
+const REMEMBERED_FADE: f32 = 0.75;
+
+// What an interactive element has input focus for, so that its response can be acted on
+pub enum Purpose {
+    Inventory(Vec<Entity>),
+    Craft(Vec<String>),
+    Examine,
+    Grab(Entity),
+    Throw(Direction),
+    Container(Entity, Option<Entity>, Vec<Entity>), // A container, what it lies in, and its contents
+    Trade(Entity, Vec<Entity>, Vec<Entity>), // A trader, what it has in stock, and what can be sold
+    LevelUp,
+    Look,
+    Quit,
+}
+
+pub struct Focus {
+    pub element: Box<dyn Interactive>,
+    pub purpose: Purpose,
+}
 
 pub struct Game {
     pub player: Entity,
@@ -54,8 +106,11 @@ pub struct Game {
     pub world: World,
     pub resources: Resources,
     pub schedule: Schedule,
+    pub streamer: Streamer,
     pub inventory_pane: List,
-    pub log_pane: Arc<RwLock<List>>,
+    pub log_pane: Arc<RwLock<Log>>,
+    pub focus: Vec<Focus>,
+    pub screen: Screen,
 }
 
 impl Game {
@@ -67,26 +122,76 @@ impl Game {
 
         resources.insert(map.clone());
         resources.insert(rng);
+        resources.insert(Clock::default());
+
+        // The map is brought in line with any changed components before anything else runs
+        let mut schedule = Schedule::builder();
+        schedule
+            .add_system(systems::map_sync::map_sync_system())
+            .flush()
+            .add_system(systems::consistency::map_consistency_system(None))
+            .flush();
 
-        let schedule = Schedule::builder()
+        let schedule = schedule
             .add_system(systems::spawn::spawn_system())
+            .add_system(systems::loot::loot_system())
+            .add_system(systems::identify::identify_system())
+            .add_system(systems::listen::listen_system(0))
             .add_system(systems::displacement::displacement_system())
             .add_system(systems::velocity::velocity_system())
+            .add_system(systems::encumbrance::encumbrance_system())
             .add_system(systems::grab::grab_system())
+            .add_system(systems::take::take_system())
+            .add_system(systems::stow::stow_system())
+            .add_system(systems::buy::buy_system())
+            .add_system(systems::sell::sell_system())
             .add_system(systems::throw::throw_system())
+            .add_system(systems::operate::operate_system())
+            .add_system(systems::consume::consume_system())
+            .add_system(systems::examine::examine_system())
+            .add_system(systems::craft::craft_system())
+            .add_system(systems::pressure_plate::pressure_plate_system())
+            .add_system(systems::impact::impact_system())
+            .add_system(systems::tread::tread_system())
+            .add_system(systems::shatter::shatter_system())
+            .add_system(systems::surface::surface_system(0))
+            .add_system(systems::fire::fire_system(0))
+            .add_system(systems::gas::gas_system(0))
+            .add_system(systems::needs::needs_system(0))
+            .add_system(systems::spoilage::spoilage_system(0))
+            .add_system(systems::status::status_system(0))
+            .add_system(systems::hearing::hearing_system())
+            .add_system(systems::stain::stain_system(0))
+            .add_system(systems::memory::memory_system())
+            .add_system(systems::camera::camera_system())
+            .add_system(systems::stack::stack_system())
             .build();
 
         // Interface
-        let inventory_pane = List::new(INVENTORY_PANE_POSITION, INVENTORY_PANE_SIZE, 0);
-        let log_pane = Arc::new(RwLock::new(List::new(
-            EVENT_LOG_PANE_POSITION,
-            EVENT_LOG_PANE_SIZE,
+        let screen = Screen::new(WINDOW_DIMENSIONS);
+        let inventory_pane = List::new(
+            screen.inventory_pane.position,
+            screen.inventory_pane.scale,
             0,
+        );
+        let log_pane = Arc::new(RwLock::new(Log::new(
+            screen.event_log_pane.position,
+            screen.event_log_pane.scale,
+            EVENT_LOG_CAPACITY,
         )));
+        log_pane
+            .write()
+            .unwrap()
+            .resize(screen.event_log_pane, screen.bounds);
+        log_pane.write().unwrap().add(
+            0,
+            Category::System,
+            label!["Welcome to the caves. Press H for message history"],
+        );
         resources.insert(log_pane.clone());
 
         // Map generation
-        let dungeon = Dungeon::new(
+        let mut dungeon = Dungeon::new(
             &mut world,
             map.clone(),
             (0, 0),
@@ -95,13 +200,28 @@ impl Game {
             &WALLS["cave"],
         );
         dungeon.generate_caves(25);
-
+        dungeon.furnish(
+            &FEATURES["door"],
+            &FEATURES["lever"],
+            &FEATURES["pressure_plate"],
+            &WALLS["cracked_cave"],
+        );
+        dungeon.scatter(&COATINGS["water"], 0.004);
+        dungeon.scatter(&COATINGS["moss"], 0.01);
+        dungeon.scatter(&COATINGS["oil"], 0.001);
+        dungeon.scatter(&COATINGS["blood"], 0.001);
+        dungeon.scatter(&COATINGS["ooze"], 0.001);
+        dungeon.scatter(&COATINGS["phosphor"], 0.001);
+        dungeon.settle(&CREATURES["trader"], 1);
+
+        // The player starts on a floor with nothing, such as the trader, standing on it
         let player_start = resources
             .get_mut::<RandomNumberGenerator>()
             .unwrap()
             .random_slice_entry(
                 <(&Tile, &Position)>::query()
                     .iter(&world)
+                    .filter(|(_, position)| !map.read().unwrap().blocks_movement(position.vector))
                     .collect::<Vec<_>>()
                     .as_slice(),
             )
@@ -120,17 +240,30 @@ impl Game {
                 character: '@',
                 colour: player_colour,
             },
-            CameraFocus,
+            CameraFocus::default(),
+            Layer::Creature,
             Position {
                 vector: player_start,
             },
-            Memory::default(),
+            Memory::new(map_dimensions.into()),
         ));
-        map.write().unwrap()[player_start].push(Object {
-            entity: player,
-            block_movement: false,
-            block_fov: false,
-        });
+        if let Some(mut entry) = world.entry(player) {
+            entry.add_component(Player);
+            entry.add_component(Hearing::default());
+            entry.add_component(Attributes::default());
+            entry.add_component(Experience::default());
+            entry.add_component(Skills::default());
+            entry.add_component(Needs::default());
+            entry.add_component(Identified::default());
+        }
+        map.write().unwrap().place(
+            player_start,
+            Object::from_components(player, Some(&Layer::Creature), None, None),
+        );
+
+        let mut camera = Camera::new(screen.viewport, map_dimensions.into());
+        camera.snap(player_start);
+        resources.insert(camera);
 
         Game {
             player,
@@ -138,8 +271,626 @@ impl Game {
             world,
             resources,
             schedule,
+            streamer: Streamer::new(CHUNKS_DIRECTORY_PATH),
             inventory_pane,
             log_pane,
+            focus: Vec::new(),
+            screen,
+        }
+    }
+
+    // Lay the interface out again for a new console size
+    pub fn resize(&mut self, size: (i32, i32)) {
+        self.screen = Screen::new(size);
+        self.resources
+            .get_mut::<Camera>()
+            .unwrap()
+            .set_viewport(self.screen.viewport);
+        self.inventory_pane.resize(self.screen.inventory_pane);
+        self.log_pane
+            .write()
+            .unwrap()
+            .resize(self.screen.event_log_pane, self.screen.bounds);
+
+        for focus in self.focus.iter_mut() {
+            focus.element.fit(&self.screen);
+        }
+    }
+
+    // The map position shown at a point on the screen
+    pub fn screen_to_world(&self, screen: (i32, i32)) -> Vector<i32> {
+        self.resources
+            .get::<Camera>()
+            .unwrap()
+            .screen_to_world(screen)
+    }
+
+    // The point on the screen a map position is shown at
+    pub fn world_to_screen(&self, point: Vector<i32>) -> (i32, i32) {
+        self.resources
+            .get::<Camera>()
+            .unwrap()
+            .world_to_screen(point)
+    }
+
+    pub fn open(&mut self, mut element: Box<dyn Interactive>, purpose: Purpose) {
+        element.fit(&self.screen);
+        self.focus.push(Focus { element, purpose });
+    }
+
+    // How far the player can currently see
+    pub fn sight_radius(&self) -> i32 {
+        self.world
+            .entry_ref(self.player)
+            .unwrap()
+            .get_component::<StatusEffects>()
+            .map_or(FOV_RADIUS, |statuses| statuses.sight(FOV_RADIUS))
+    }
+
+    // Describe what the player sees, or remembers, at a map position
+    pub fn describe(&self, point: Vector<i32>) -> Label {
+        let player = self.world.entry_ref(self.player).unwrap();
+        let position = player.get_component::<Position>().unwrap();
+        let map = self.map.read().unwrap();
+
+        let visible = field_of_view(position.vector.into(), self.sight_radius(), map.deref())
+            .into_iter()
+            .any(|p| Vector::from(p) == point);
+
+        if visible {
+            if let Some(top_obj) = map.top(point) {
+                let entry = self.world.entry_ref(top_obj.entity).unwrap();
+                let glyph = entry
+                    .get_component::<Glyph>()
+                    .map_or(Glyph::default(), |g| *g);
+                let description = entry
+                    .get_component::<Description>()
+                    .cloned()
+                    .unwrap_or_default();
+
+                return label![
+                    "You see ",
+                    glyph,
+                    " ",
+                    (
+                        quantity::label(&self.world, top_obj.entity, &description.name),
+                        glyph.colour
+                    ),
+                    ". ",
+                    description.description
+                ];
+            }
+        } else if let Ok(memory) = player.get_component::<Memory>() {
+            let clock = self.resources.get::<Clock>().unwrap();
+
+            if let Some(recollection) = memory.spatial.read().unwrap().get(point) {
+                return label!["You remember ", recollection.glyph(&clock), " here"];
+            }
+        }
+
+        label!["You cannot see there"]
+    }
+
+    pub fn open_inventory(&mut self) {
+        let player = self.world.entry_ref(self.player).unwrap();
+        let equipment = player.get_component::<Equipment>().unwrap();
+        let mut items = Vec::new();
+
+        if let Some(held_entity) = equipment.held {
+            items.push(held_entity);
+        }
+
+        if let Some(storage_entity) = equipment.storage {
+            if let Ok(storage_entry) = self.world.entry_ref(storage_entity) {
+                if let Ok(storage) = storage_entry.get_component::<Storage>() {
+                    items.extend(storage.objects.iter().cloned());
+                }
+            }
+        }
+
+        let mut options = items
+            .iter()
+            .map(|item| {
+                let entry = self.world.entry_ref(*item).unwrap();
+                let glyph = *entry.get_component::<Glyph>().unwrap();
+                let name = quantity::label(
+                    &self.world,
+                    *item,
+                    &entry.get_component::<Description>().unwrap().name,
+                );
+                let mut option = label![glyph, " ", (name, glyph.colour)];
+
+                if Some(*item) == equipment.held {
+                    option += label![" (held)"];
+                }
+
+                option
+            })
+            .collect::<Vec<_>>();
+
+        // The last option turns to combining what is carried
+        if !items.is_empty() {
+            options.push(label!["Combine..."]);
+        }
+
+        self.open(
+            Box::new(Menu::new("Inventory", options)),
+            Purpose::Inventory(items),
+        );
+    }
+
+    // Browse what is inside a container, which may itself lie inside another
+    pub fn open_container(&mut self, container: Entity, parent: Option<Entity>) {
+        let name = |world: &World, entity: Entity| {
+            let entry = world.entry_ref(entity).unwrap();
+            let glyph = *entry.get_component::<Glyph>().unwrap();
+            let name = quantity::label(
+                world,
+                entity,
+                &entry.get_component::<Description>().unwrap().name,
+            );
+
+            label![glyph, " ", (name, glyph.colour)]
+        };
+
+        let objects = self
+            .world
+            .entry_ref(container)
+            .unwrap()
+            .get_component::<Storage>()
+            .unwrap()
+            .objects
+            .clone();
+        let held = self
+            .world
+            .entry_ref(self.player)
+            .unwrap()
+            .get_component::<Equipment>()
+            .unwrap()
+            .held
+            .filter(|held| !encloses(&self.world, *held, container));
+
+        let mut options = objects
+            .iter()
+            .map(|object| name(&self.world, *object))
+            .collect::<Vec<_>>();
+
+        // After the contents come putting in what is held, and taking out the container itself
+        if let Some(held) = held {
+            options.push(label!["Put in "] + name(&self.world, held));
+        }
+
+        if parent.is_some() {
+            options.push(label!["Take "] + name(&self.world, container));
+        }
+
+        let title = self
+            .world
+            .entry_ref(container)
+            .unwrap()
+            .get_component::<Description>()
+            .unwrap()
+            .name
+            .clone();
+
+        if options.is_empty() {
+            self.open(
+                Box::new(Dialog::new(title, label!["It is empty"])),
+                Purpose::Examine,
+            );
+        } else {
+            self.open(
+                Box::new(Menu::new(title, options)),
+                Purpose::Container(container, parent, objects),
+            );
+        }
+    }
+
+    // Offer what a trader has in stock, followed by whatever the player could sell to it
+    pub fn open_trade(&mut self, trader: Entity) {
+        let name = |world: &World, entity: Entity, price: u32| {
+            let entry = world.entry_ref(entity).unwrap();
+            let glyph = *entry.get_component::<Glyph>().unwrap();
+            let name = quantity::label(
+                world,
+                entity,
+                &entry.get_component::<Description>().unwrap().name,
+            );
+
+            label![
+                glyph,
+                " ",
+                (name, glyph.colour),
+                format!(" for {} coins", price)
+            ]
+        };
+
+        let player = self.world.entry_ref(self.player).unwrap();
+        let equipment = player.get_component::<Equipment>().unwrap();
+        let coins = value::coins(&self.world, equipment);
+
+        let stock = self
+            .world
+            .entry_ref(trader)
+            .unwrap()
+            .get_component::<Storage>()
+            .map_or(Vec::new(), |storage| storage.objects.clone())
+            .into_iter()
+            .filter_map(|item| asking_price(&self.world, item).map(|price| (item, price)))
+            .collect::<Vec<_>>();
+
+        // Coins are not for sale, and neither is anything with something still inside it
+        let mut carried = equipment.held.into_iter().collect::<Vec<_>>();
+
+        if let Some(storage) = equipment.storage {
+            if let Ok(storage) = <&Storage>::query().get(&self.world, storage) {
+                carried.extend(storage.objects.iter().cloned());
+            }
+        }
+
+        let wares = carried
+            .into_iter()
+            .filter(|item| !tagged(&self.world, *item, CURRENCY))
+            .filter(|item| {
+                <&Storage>::query()
+                    .get(&self.world, *item)
+                    .map_or(true, |storage| storage.objects.is_empty())
+            })
+            .filter_map(|item| offer(&self.world, item).map(|price| (item, price)))
+            .collect::<Vec<_>>();
+
+        let options = stock
+            .iter()
+            .map(|(item, price)| label!["Buy "] + name(&self.world, *item, *price))
+            .chain(
+                wares
+                    .iter()
+                    .map(|(item, price)| label!["Sell "] + name(&self.world, *item, *price)),
+            )
+            .collect::<Vec<_>>();
+
+        let title = format!(
+            "{} (you have {} coins)",
+            self.world
+                .entry_ref(trader)
+                .unwrap()
+                .get_component::<Description>()
+                .unwrap()
+                .name,
+            coins
+        );
+
+        if options.is_empty() {
+            self.open(
+                Box::new(Dialog::new(title, label!["There is nothing to trade"])),
+                Purpose::Examine,
+            );
+        } else {
+            self.open(
+                Box::new(Menu::new(title, options)),
+                Purpose::Trade(
+                    trader,
+                    stock.into_iter().map(|(item, _)| item).collect(),
+                    wares.into_iter().map(|(item, _)| item).collect(),
+                ),
+            );
+        }
+    }
+
+    // Offer the recipes that can be made from what the player carries
+    pub fn open_crafting(&mut self) {
+        let player = self.world.entry_ref(self.player).unwrap();
+        let at_hand = at_hand(&self.world, player.get_component::<Equipment>().unwrap());
+
+        let mut recipes = RECIPES
+            .iter()
+            .filter(|(_, recipe)| recipe.inputs_from(&at_hand).is_some())
+            .map(|(name, _)| name.clone())
+            .collect::<Vec<_>>();
+        recipes.sort();
+
+        if recipes.is_empty() {
+            let turn = self.resources.get::<Clock>().unwrap().turn;
+            self.log_pane.write().unwrap().add(
+                turn,
+                Category::Item,
+                label!["You have nothing that can be combined"],
+            );
+
+            return;
+        }
+
+        let options = recipes
+            .iter()
+            .map(|name| {
+                RECIPES[name]
+                    .outputs
+                    .iter()
+                    .filter_map(|output| ITEMS.get(output))
+                    .fold(Label::from(""), |option, item| {
+                        option
+                            + label![
+                                item.glyph,
+                                " ",
+                                (item.description.name.as_str(), item.glyph.colour),
+                                " "
+                            ]
+                    })
+            })
+            .collect();
+
+        self.open(
+            Box::new(Menu::new("Combine", options)),
+            Purpose::Craft(recipes),
+        );
+    }
+
+    // Offer the attribute points from any levels gained
+    pub fn open_level_up(&mut self) {
+        let level = match self
+            .world
+            .entry_ref(self.player)
+            .unwrap()
+            .get_component::<Experience>()
+        {
+            Ok(experience) if experience.unspent > 0 => experience.level,
+            _ => return,
+        };
+
+        self.open(
+            Box::new(Menu::new(
+                format!("Level {}", level),
+                vec![
+                    label!["+1 Strength"],
+                    label!["+1 Agility"],
+                    label!["+1 Perception"],
+                ],
+            )),
+            Purpose::LevelUp,
+        );
+    }
+
+    pub fn open_look(&mut self) {
+        let player_position = self
+            .world
+            .entry_ref(self.player)
+            .unwrap()
+            .get_component::<Position>()
+            .unwrap()
+            .vector;
+
+        let screen = self.world_to_screen(player_position);
+
+        self.open(Box::new(Cursor::new(screen)), Purpose::Look);
+    }
+
+    // Have the player take an action, spending however long it takes them
+    pub fn act<T: storage::Component>(&mut self, action: T) {
+        let mut player = self.world.entry(self.player).unwrap();
+        let cost = player
+            .get_component::<StatusEffects>()
+            .map_or(2, |statuses| statuses.action_cost());
+
+        player.add_component(action);
+        self.resources.get_mut::<Clock>().unwrap().spend(cost);
+    }
+
+    // Reach for something, first asking how many to take if it is a stack. Containers that are
+    // fixed in place are opened instead
+    pub fn grab(&mut self, entity: Entity) {
+        let fixed = self.world.entry_ref(entity).is_ok_and(|entry| {
+            entry.get_component::<Storage>().is_ok() && entry.get_component::<Weight>().is_err()
+        });
+
+        if fixed {
+            return self.open_container(entity, None);
+        }
+
+        match quantity::count(&self.world, entity) {
+            count if count > 1 => self.open(
+                Box::new(QuantityPicker::new("Pick up how many?", 1, count)),
+                Purpose::Grab(entity),
+            ),
+            _ => self.act(Grab { entity }),
+        }
+    }
+
+    // Throw whatever is held, first asking how many to throw if it is a stack
+    pub fn throw(&mut self, direction: Direction) {
+        let held = self
+            .world
+            .entry_ref(self.player)
+            .unwrap()
+            .get_component::<Equipment>()
+            .unwrap()
+            .held;
+
+        match held.map_or(1, |held| quantity::count(&self.world, held)) {
+            count if count > 1 => self.open(
+                Box::new(QuantityPicker::new("Throw how many?", 1, count)),
+                Purpose::Throw(direction),
+            ),
+            _ => self.act(Throw {
+                direction,
+                rest: None,
+            }),
+        }
+    }
+
+    // Act on the response of a closed element
+    pub fn respond(&mut self, ctx: &mut BTerm, purpose: Purpose, response: Response) {
+        match (purpose, response) {
+            (Purpose::Inventory(items), Response::Selected(index)) if index == items.len() => {
+                self.open_crafting()
+            }
+
+            (Purpose::Craft(recipes), Response::Selected(index)) => self.act(Craft {
+                recipe: recipes[index].clone(),
+            }),
+
+            // Only what is taken is split from the stack, and the rest is left where it lies
+            (Purpose::Grab(entity), Response::Quantity(count)) => {
+                let position = self
+                    .world
+                    .entry_ref(entity)
+                    .ok()
+                    .and_then(|entry| entry.get_component::<Position>().ok().copied());
+                let taken = position
+                    .and_then(|position| {
+                        split(&mut self.world, &self.map, entity, count, position.vector)
+                    })
+                    .unwrap_or(entity);
+
+                self.act(Grab { entity: taken });
+            }
+
+            // Whatever is not thrown stays in hand
+            (Purpose::Throw(direction), Response::Quantity(count)) => {
+                let player = self.world.entry_ref(self.player).unwrap();
+                let position = player.get_component::<Position>().unwrap().vector;
+                let held = player.get_component::<Equipment>().unwrap().held;
+                let total = held.map_or(1, |held| quantity::count(&self.world, held));
+                let rest = held.and_then(|held| {
+                    split(&mut self.world, &self.map, held, total - count, position)
+                });
+
+                if let Some(rest) = rest {
+                    self.map.write().unwrap().remove(rest);
+                    self.world
+                        .entry(rest)
+                        .unwrap()
+                        .remove_component::<Position>();
+                }
+
+                self.act(Throw { direction, rest });
+            }
+
+            // Containers carried are browsed rather than examined
+            (Purpose::Inventory(items), Response::Selected(index))
+                if self
+                    .world
+                    .entry_ref(items[index])
+                    .is_ok_and(|entry| entry.get_component::<Storage>().is_ok()) =>
+            {
+                let player = self.world.entry_ref(self.player).unwrap();
+                let equipment = player.get_component::<Equipment>().unwrap();
+                let parent = equipment
+                    .storage
+                    .filter(|_| equipment.held != Some(items[index]));
+
+                self.open_container(items[index], parent);
+            }
+
+            (Purpose::Container(container, parent, objects), Response::Selected(index)) => {
+                if let Some(object) = objects.get(index).copied() {
+                    let nested = self
+                        .world
+                        .entry_ref(object)
+                        .is_ok_and(|entry| entry.get_component::<Storage>().is_ok());
+
+                    if nested {
+                        self.open_container(object, Some(container));
+                    } else {
+                        self.act(Take {
+                            entity: object,
+                            container,
+                        });
+                    }
+
+                    return;
+                }
+
+                let held = self
+                    .world
+                    .entry_ref(self.player)
+                    .unwrap()
+                    .get_component::<Equipment>()
+                    .unwrap()
+                    .held
+                    .filter(|held| !encloses(&self.world, *held, container));
+
+                match (index - objects.len(), held, parent) {
+                    (0, Some(_), _) => self.act(Stow {
+                        container: Some(container),
+                    }),
+                    (_, _, Some(parent)) => self.act(Take {
+                        entity: container,
+                        container: parent,
+                    }),
+                    _ => {}
+                }
+            }
+
+            (Purpose::Inventory(items), Response::Selected(index)) => {
+                let entry = self.world.entry_ref(items[index]).unwrap();
+                let glyph = *entry.get_component::<Glyph>().unwrap();
+                let description = entry.get_component::<Description>().unwrap().clone();
+
+                // Studying something unfamiliar takes a turn, and might reveal what it is
+                if entry
+                    .get_component::<Unidentified>()
+                    .is_ok_and(|unidentified| !unidentified.examined)
+                {
+                    self.act(Examine {
+                        entity: items[index],
+                    });
+                }
+
+                self.open(
+                    Box::new(Dialog::new(
+                        description.name,
+                        label![glyph, " ", description.description],
+                    )),
+                    Purpose::Examine,
+                );
+            }
+
+            (Purpose::Trade(trader, stock, wares), Response::Selected(index)) => {
+                match stock.get(index) {
+                    Some(entity) => self.act(Buy {
+                        trader,
+                        entity: *entity,
+                    }),
+                    None => self.act(Sell {
+                        trader,
+                        entity: wares[index - stock.len()],
+                    }),
+                }
+            }
+
+            (Purpose::LevelUp, Response::Selected(index)) => {
+                let mut player = self.world.entry(self.player).unwrap();
+                let attributes = player.get_component_mut::<Attributes>().unwrap();
+                let (attribute, value) = match index {
+                    0 => ("strength", &mut attributes.strength),
+                    1 => ("agility", &mut attributes.agility),
+                    _ => ("perception", &mut attributes.perception),
+                };
+
+                *value += 1;
+                let label = label![format!("Your {} rises to {}", attribute, value)];
+
+                player.get_component_mut::<Experience>().unwrap().unspent -= 1;
+
+                let turn = self.resources.get::<Clock>().unwrap().turn;
+                self.log_pane
+                    .write()
+                    .unwrap()
+                    .add(turn, Category::World, label);
+            }
+
+            (Purpose::Quit, Response::Confirmed(true)) => ctx.quit(),
+
+            _ => {}
+        }
+    }
+
+    pub fn get_focus_input(&mut self, ctx: &mut BTerm) {
+        if let Some(mut focus) = self.focus.pop() {
+            match focus.element.input(ctx) {
+                Response::Pending => self.focus.push(focus),
+                response => self.respond(ctx, focus.purpose, response),
+            }
         }
     }
 
@@ -152,20 +903,106 @@ impl Game {
             player.get_component::<Intention>().unwrap().intent
         )
         .to_uppercase()]);
+
+        if self.resources.get::<Camera>().unwrap().mode == CameraMode::FreeLook {
+            self.inventory_pane.add(label!["FREE LOOK"]);
+        }
         self.inventory_pane.add(match player_equipment.held {
             Some(held_entity) => {
                 let held_entry = self.world.entry_ref(held_entity).unwrap();
 
+                let name = quantity::label(
+                    &self.world,
+                    held_entity,
+                    &held_entry.get_component::<Description>().unwrap().name,
+                );
+
                 label![
                     "Hands [",
                     *held_entry.get_component::<Glyph>().unwrap(),
                     " ",
-                    *held_entry.get_component::<Description>().unwrap().name,
+                    name,
                     "]"
                 ]
             }
             None => label!["Hands []"],
         });
+
+        if let Ok(attributes) = player.get_component::<Attributes>() {
+            let load = carried_weight(&self.world, player_equipment);
+            let encumbrance = player
+                .get_component::<Encumbrance>()
+                .cloned()
+                .unwrap_or_default();
+
+            self.inventory_pane.add(label![format!(
+                "STR {} AGI {} PER {}",
+                attributes.effective_strength(),
+                attributes.effective_agility(),
+                attributes.effective_perception()
+            )]);
+
+            let mut carrying = label![format!(
+                "Load {:.1}/{:.1}kg",
+                load as f32 / 1000.0,
+                attributes.carry_capacity() as f32 / 1000.0
+            )];
+
+            if encumbrance != Encumbrance::Unburdened {
+                carrying += label![format!(" ({})", encumbrance.name())];
+            }
+
+            self.inventory_pane.add(carrying);
+        }
+
+        if let Ok(experience) = player.get_component::<Experience>() {
+            self.inventory_pane.add(label![format!(
+                "Level {} ({}/{}xp)",
+                experience.level,
+                experience.points,
+                experience.next_level()
+            )]);
+        }
+
+        if let Ok(skills) = player.get_component::<Skills>() {
+            self.inventory_pane.add(label![format!(
+                "Throw {} Stealth {} Locks {}",
+                skills.throwing.level, skills.stealth.level, skills.lockpicking.level
+            )]);
+        }
+
+        if let Ok(needs) = player.get_component::<Needs>() {
+            for name in [needs.hunger_name(), needs.thirst_name()].iter().flatten() {
+                self.inventory_pane.add(label![name.to_uppercase()]);
+            }
+        }
+
+        if let Ok(statuses) = player.get_component::<StatusEffects>() {
+            for effect in &statuses.effects {
+                let mut line =
+                    label![(effect.status.name().to_uppercase(), effect.status.colour())];
+
+                if effect.magnitude > 1 {
+                    line += label![format!(" x{}", effect.magnitude)];
+                }
+
+                self.inventory_pane
+                    .add(line + label![format!(" ({})", effect.turns)]);
+            }
+        }
+
+        if let Some(Focus {
+            element,
+            purpose: Purpose::Look,
+        }) = self.focus.last()
+        {
+            let point = self.screen_to_world(element.position());
+
+            self.inventory_pane.add(label![""]);
+            self.inventory_pane.add(label!["LOOK"]);
+            self.inventory_pane.add(self.describe(point));
+        }
+
         self.inventory_pane.render(ctx);
         self.inventory_pane.clear();
 
@@ -183,12 +1020,8 @@ impl Game {
         let map = self.map.read().unwrap();
 
         for position in positions {
-            let objects = map
-                .get_objects(Vector::from((position.0, position.1)))
-                .unwrap();
-
-            if !objects.is_empty() {
-                let cell_entity = objects.iter().last().unwrap().entity;
+            if let Some(cell) = map.top(Vector::from((position.0, position.1))) {
+                let cell_entity = cell.entity;
                 let cell_pos_opt;
                 let cell_light_opt;
 
@@ -208,10 +1041,8 @@ impl Game {
 
                     for point in illuminated_points {
                         let point_pos: Vector<i32> = point.into();
-                        let objects_at_point = map.get_objects(point_pos).unwrap();
-
-                        if !objects_at_point.is_empty() {
-                            let top_obj_entity = objects_at_point.iter().last().unwrap().entity;
+                        if let Some(top_obj) = map.top(point_pos) {
+                            let top_obj_entity = top_obj.entity;
                             let mut top_obj_entry = self.world.entry(top_obj_entity).unwrap();
 
                             let cell_dist = position.vector.distance(point_pos);
@@ -241,17 +1072,22 @@ impl Game {
             let background_bg = RGB::from((10, 10, 15));
             let mut visible_points = Vec::default();
 
-            if let Some((_, position)) = <(&CameraFocus, &Position)>::query()
-                .iter(&self.world)
-                .collect::<Vec<_>>()
-                .first()
-            {
+            let camera = *self.resources.get::<Camera>().unwrap();
+            let player_position = self
+                .world
+                .entry_ref(self.player)
+                .unwrap()
+                .get_component::<Position>()
+                .ok()
+                .cloned();
+
+            if let Some(position) = player_position {
                 let origin = Point {
-                    x: position.vector.0 as i32,
-                    y: position.vector.1 as i32,
+                    x: position.vector.0,
+                    y: position.vector.1,
                 };
                 visible_points.append(
-                    &mut field_of_view(origin, 10, map.deref())
+                    &mut field_of_view(origin, self.sight_radius(), map.deref())
                         .into_iter()
                         .map(|p| Vector::from(p))
                         .collect::<Vec<_>>(),
@@ -260,15 +1096,17 @@ impl Game {
                 self.apply_illumination(&mut commands, &visible_points, ctx);
             }
 
-            if let Some((_, position, _)) = <(&CameraFocus, &Position, &Glyph)>::query()
-                .iter(&self.world)
-                .collect::<Vec<_>>()
-                .first()
             {
                 ctx.cls_bg(background_bg);
 
                 for point in &visible_points {
-                    if let Some(top_obj) = map[*point].last() {
+                    if !camera.shows(*point) {
+                        continue;
+                    }
+
+                    let screen_point = camera.world_to_screen(*point);
+
+                    if let Some(top_obj) = map.top(*point) {
                         let top_obj_entry = self.world.entry_ref(top_obj.entity).unwrap();
                         let top_obj_glyph = top_obj_entry
                             .get_component::<Glyph>()
@@ -304,10 +1142,8 @@ impl Game {
                         }
 
                         ctx.set(
-                            WINDOW_CENTER.0 as i32
-                                - (position.vector.0 as i32 - point.0)
-                                - (GUI_WIDTH / 2) as i32,
-                            WINDOW_CENTER.1 as i32 - (position.vector.1 as i32 - point.1),
+                            screen_point.0,
+                            screen_point.1,
                             top_obj_rgba,
                             background_bg,
                             to_cp437(top_obj_glyph.character),
@@ -316,44 +1152,37 @@ impl Game {
                 }
             }
 
-            let mut focused_entity = None;
-            if let Some((e, _, position, _, memory)) =
-                <(Entity, &CameraFocus, &Position, &Glyph, &Memory)>::query()
-                    .iter(&self.world)
-                    .collect::<Vec<_>>()
-                    .first()
+            if let Ok(memory) = self
+                .world
+                .entry_ref(self.player)
+                .unwrap()
+                .get_component::<Memory>()
             {
-                focused_entity = Some(**e);
+                let clock = *self.resources.get::<Clock>().unwrap();
+                let spatial = memory.spatial.read().unwrap();
+                let visible_points = visible_points.iter().cloned().collect::<HashSet<_>>();
 
-                for point in &memory.spatial {
-                    if let Some(top_obj) = map[*point].last() {
-                        let top_obj_entry = self.world.entry_ref(top_obj.entity).unwrap();
-                        let top_obj_glyph = top_obj_entry
-                            .get_component::<Glyph>()
-                            .map_or(Glyph::default(), |g| *g);
+                for (x, y) in camera.viewport.points() {
+                    let point = camera.screen_to_world((x, y));
 
-                        if !visible_points.contains(point) {
-                            ctx.set(
-                                WINDOW_CENTER.0 as i32
-                                    - (position.vector.0 as i32 - point.0)
-                                    - (GUI_WIDTH / 2) as i32,
-                                WINDOW_CENTER.1 as i32 - (position.vector.1 as i32 - point.1),
-                                (30, 30, 35),
-                                background_bg,
-                                to_cp437(top_obj_glyph.character),
-                            );
-                        }
+                    if visible_points.contains(&point) {
+                        continue;
                     }
-                }
-            }
 
-            if let Some(focused_entity) = focused_entity {
-                let mut focused_entity = self.world.entry_mut(focused_entity).unwrap();
-                let memory = focused_entity.get_component_mut::<Memory>().unwrap();
+                    // Remembered tiles keep the colour they were seen in, faded into the dark
+                    if let Some(recollection) = spatial.get(point) {
+                        let glyph = recollection.glyph(&clock);
 
-                for vec in visible_points {
-                    if !memory.spatial.contains(&vec) {
-                        memory.spatial.push(vec);
+                        ctx.set(
+                            x,
+                            y,
+                            glyph
+                                .colour
+                                .rgba
+                                .lerp(REMEMBERED_COLOUR.into(), REMEMBERED_FADE),
+                            background_bg,
+                            to_cp437(glyph.character),
+                        );
                     }
                 }
             }
@@ -363,13 +1192,36 @@ impl Game {
     }
 
     pub fn get_input(&mut self, ctx: &mut BTerm) {
+        // Opening an element needs the world and map, so is done before they are borrowed
+        match ctx.key {
+            Some(VirtualKeyCode::I) => return self.open_inventory(),
+            Some(VirtualKeyCode::X) => return self.open_look(),
+            Some(VirtualKeyCode::Escape) => {
+                return self.open(Box::new(Prompt::new(label!["Really quit?"])), Purpose::Quit)
+            }
+            _ => {}
+        }
+
         let mut player = self.world.entry(self.player).unwrap();
         let player_position = player.get_component::<Position>().unwrap();
         let player_intention = player.get_component::<Intention>().unwrap();
         let map = self.map.write().unwrap();
-        let objects_at_player_tile = &map[player_position.vector];
+        let mut walk = None;
+        let mut grab = None;
+        let mut throw = None;
 
         if let Some(key) = ctx.key {
+            // Actions that take a turn. Walking, grabbing and throwing take theirs once it is
+            // settled whether there is a trader in the way, or how many things are grabbed or
+            // thrown
+            if matches!(key, VirtualKeyCode::E | VirtualKeyCode::B) {
+                let cost = player
+                    .get_component::<StatusEffects>()
+                    .map_or(2, |statuses| statuses.action_cost());
+
+                self.resources.get_mut::<Clock>().unwrap().spend(cost);
+            }
+
             match key {
                 VirtualKeyCode::Tab => player.add_component(Intention {
                     intent: Intent::Walk,
@@ -391,22 +1243,19 @@ impl Game {
                     } {
                         match player_intention.intent {
                             Intent::Walk => {
-                                player.add_component(Displacement::from(direction));
+                                let ahead = player_position.vector + direction.as_unit_vector();
+                                walk = Some((direction, map.top(ahead).map(|obj| obj.entity)));
                             }
 
                             Intent::Grab => {
-                                player.add_component(Grab {
-                                    entity: map
-                                        [player_position.vector + direction.as_unit_vector()]
-                                    .last()
-                                    .unwrap()
-                                    .entity,
-                                });
+                                grab = Some(
+                                    map.top(player_position.vector + direction.as_unit_vector())
+                                        .unwrap()
+                                        .entity,
+                                );
                             }
 
-                            Intent::Throw => {
-                                player.add_component(Throw { direction });
-                            }
+                            Intent::Throw => throw = Some(direction),
 
                             _ => {}
                         }
@@ -415,72 +1264,140 @@ impl Game {
 
                 VirtualKeyCode::F => match player_intention.intent {
                     Intent::Walk | Intent::Grab => {
-                        player.add_component(Grab {
-                            entity: objects_at_player_tile[1].entity,
-                        });
+                        // Reach for whatever lies beneath the player
+                        grab = map
+                            .top_beneath(player_position.vector, Layer::Creature)
+                            .map(|obj| obj.entity);
                     }
                     _ => {}
                 },
 
-                VirtualKeyCode::B => {
-                    let mut label = Label::from("You ");
-                    let player = self.world.entry_ref(self.player).unwrap();
-                    let player_equipment = player.get_component::<Equipment>().unwrap();
-
-                    if let Some(held_item_entity) = player_equipment.held {
-                        let held_item_glyph;
-                        let held_item_description;
-
-                        {
-                            let held_item_entry = self.world.entry_ref(held_item_entity).unwrap();
-                            held_item_glyph = *held_item_entry.get_component::<Glyph>().unwrap();
-                            held_item_description = held_item_entry
-                                .get_component::<Description>()
-                                .unwrap()
-                                .clone();
-                        }
+                // Eat or drink whatever is held, or else drink from whatever is underfoot
+                VirtualKeyCode::E => {
+                    let held = player.get_component::<Equipment>().unwrap().held;
+                    let underfoot = map
+                        .get_objects(player_position.vector)
+                        .into_iter()
+                        .flatten()
+                        .find(|obj| obj.layer == Layer::Surface)
+                        .map(|obj| obj.entity);
 
-                        label += if let Some(storage_entity) = player_equipment.storage {
-                            let mut storage_entry = self.world.entry_mut(storage_entity).unwrap();
-                            let storage_description = storage_entry
-                                .get_component::<Description>()
-                                .unwrap()
-                                .clone();
-                            let storage_storage =
-                                storage_entry.get_component_mut::<Storage>().unwrap();
-
-                            storage_storage.objects.push(held_item_entity);
-
-                            label![
-                                "place the ",
-                                (held_item_description.name, held_item_glyph.colour),
-                                " in your ",
-                                storage_description.name
-                            ]
-                        } else {
-                            label![
-                                "have no place to store the ",
-                                (held_item_description.name, held_item_glyph.colour)
-                            ]
-                        };
-                    } else {
-                        label += label!["are not holding anything"]
+                    if let Some(entity) = held.or(underfoot) {
+                        player.add_component(Consume { entity });
                     }
+                }
 
-                    self.log_pane.write().unwrap().add(label);
+                // Put whatever is held into whatever is worn for storage
+                VirtualKeyCode::B => player.add_component(Stow { container: None }),
+
+                // Camera
+                VirtualKeyCode::V => self
+                    .resources
+                    .get_mut::<Camera>()
+                    .unwrap()
+                    .toggle_free_look(),
+                VirtualKeyCode::Up
+                | VirtualKeyCode::Down
+                | VirtualKeyCode::Left
+                | VirtualKeyCode::Right => {
+                    let mut camera = self.resources.get_mut::<Camera>().unwrap();
+
+                    if camera.mode == CameraMode::FreeLook {
+                        camera.pan(match key {
+                            VirtualKeyCode::Up => Vector(0, -1),
+                            VirtualKeyCode::Down => Vector(0, 1),
+                            VirtualKeyCode::Left => Vector(-1, 0),
+                            _ => Vector(1, 0),
+                        });
+                    }
                 }
 
+                // Message log
+                VirtualKeyCode::PageUp => self.log_pane.write().unwrap().scroll_up(5),
+                VirtualKeyCode::PageDown => self.log_pane.write().unwrap().scroll_down(5),
+                VirtualKeyCode::H => self.log_pane.write().unwrap().toggle_history(),
+                VirtualKeyCode::L => self.log_pane.write().unwrap().cycle_filter(),
+
                 _ => {}
             }
         }
+
+        drop(map);
+
+        // Walking into a trader offers to trade with it
+        if let Some((direction, ahead)) = walk {
+            let trader = ahead.filter(|entity| {
+                self.world
+                    .entry_ref(*entity)
+                    .is_ok_and(|entry| entry.get_component::<Trader>().is_ok())
+            });
+
+            match trader {
+                Some(trader) => self.open_trade(trader),
+                None => self.act(Displacement::from(direction)),
+            }
+        }
+
+        if let Some(entity) = grab {
+            self.grab(entity);
+        }
+
+        if let Some(direction) = throw {
+            self.throw(direction);
+        }
     }
 }
 
 impl GameState for Game {
     fn tick(&mut self, ctx: &mut BTerm) {
+        let (width, height) = ctx.get_char_size();
+        if (width as i32, height as i32) != self.screen.size() {
+            self.resize((width as i32, height as i32));
+        }
+
+        self.resources.get_mut::<Clock>().unwrap().pass();
         self.schedule.execute(&mut self.world, &mut self.resources);
-        self.render_world(ctx);
-        self.render_interface(ctx);
-        self.get_input(ctx);
+
+        // Only the chunks around the player are kept in the world
+        let player_position = self
+            .world
+            .entry_ref(self.player)
+            .unwrap()
+            .get_component::<Position>()
+            .unwrap()
+            .vector;
+        if let Err(error) = self
+            .streamer
+            .stream(&mut self.world, &self.map, player_position)
+        {
+            let turn = self.resources.get::<Clock>().unwrap().turn;
+            self.log_pane
+                .write()
+                .unwrap()
+                .add(turn, Category::System, label![error.to_string()]);
+        }
+
+        if self.log_pane.read().unwrap().is_history() {
+            ctx.cls();
+            self.log_pane.read().unwrap().render(ctx);
+        } else {
+            self.render_world(ctx);
+            self.render_interface(ctx);
+
+            for focus in &self.focus {
+                focus.element.render(ctx);
+            }
+        }
+
+        // Levels gained are spent before play goes on
+        if self.focus.is_empty() {
+            self.open_level_up();
+        }
+
+        if self.focus.is_empty() {
+            self.get_input(ctx);
+        } else {
+            self.get_focus_input(ctx);
+        }
     }
 }
@@ -0,0 +1,106 @@
+use bracket_lib::prelude::line2d_bresenham;
+use vector::Vector;
+
+// Every point within a rectangle, row by row
+pub fn rect(position: Vector<i32>, size: Vector<i32>) -> impl Iterator<Item = Vector<i32>> {
+    (position.1..position.1 + size.1.max(0))
+        .flat_map(move |y| (position.0..position.0 + size.0.max(0)).map(move |x| Vector(x, y)))
+}
+
+// Every point within a radius of a centre, row by row
+pub fn circle(centre: Vector<i32>, radius: i32) -> impl Iterator<Item = Vector<i32>> {
+    let radius = radius.max(0);
+
+    rect(
+        centre - Vector(radius, radius),
+        Vector(radius * 2 + 1, radius * 2 + 1),
+    )
+    .filter(move |point| {
+        let offset = *point - centre;
+
+        offset.0 * offset.0 + offset.1 * offset.1 <= radius * radius
+    })
+}
+
+// The points on a line between two points, including both ends
+pub fn line(from: Vector<i32>, to: Vector<i32>) -> impl Iterator<Item = Vector<i32>> {
+    let mut points = line2d_bresenham(from.into(), to.into());
+    points.dedup(); // A line of one point is otherwise plotted twice
+
+    points.into_iter().map(Vector::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::collections::HashSet;
+
+    #[test]
+    fn rect_covers_each_point_once_in_row_order() {
+        let mut rng = StdRng::seed_from_u64(35);
+
+        for _ in 0..200 {
+            let position = Vector(rng.gen_range(-20, 20), rng.gen_range(-20, 20));
+            let size = Vector(rng.gen_range(-2, 12), rng.gen_range(-2, 12));
+            let points = rect(position, size).collect::<Vec<_>>();
+
+            assert_eq!(points.len(), (size.0.max(0) * size.1.max(0)) as usize);
+            assert_eq!(points.iter().collect::<HashSet<_>>().len(), points.len());
+            assert!(points
+                .windows(2)
+                .all(|pair| (pair[0].1, pair[0].0) < (pair[1].1, pair[1].0)));
+            assert!(points.iter().all(|point| point.0 >= position.0
+                && point.1 >= position.1
+                && point.0 < position.0 + size.0
+                && point.1 < position.1 + size.1));
+        }
+    }
+
+    #[test]
+    fn circle_holds_exactly_the_points_within_its_radius() {
+        let mut rng = StdRng::seed_from_u64(35);
+
+        for _ in 0..200 {
+            let centre = Vector(rng.gen_range(-20, 20), rng.gen_range(-20, 20));
+            let radius = rng.gen_range(-2, 10);
+            let points = circle(centre, radius).collect::<HashSet<_>>();
+
+            // A negative radius is taken as zero
+            let radius = radius.max(0);
+            let bound = radius + 1;
+
+            for point in rect(
+                centre - Vector(bound, bound),
+                Vector(bound * 2 + 1, bound * 2 + 1),
+            ) {
+                let offset = point - centre;
+                let within = offset.0 * offset.0 + offset.1 * offset.1 <= radius * radius;
+
+                assert_eq!(points.contains(&point), within);
+            }
+
+            assert!(points.contains(&centre));
+        }
+    }
+
+    #[test]
+    fn line_joins_its_ends_one_step_at_a_time() {
+        let mut rng = StdRng::seed_from_u64(35);
+
+        for _ in 0..200 {
+            let from = Vector(rng.gen_range(-20, 20), rng.gen_range(-20, 20));
+            let to = Vector(rng.gen_range(-20, 20), rng.gen_range(-20, 20));
+            let points = line(from, to).collect::<Vec<_>>();
+            let span = (to.0 - from.0).abs().max((to.1 - from.1).abs());
+
+            assert_eq!(points.first(), Some(&from));
+            assert_eq!(points.last(), Some(&to));
+            assert_eq!(points.len(), span as usize + 1);
+            assert!(points.windows(2).all(
+                |pair| (pair[1].0 - pair[0].0).abs() <= 1 && (pair[1].1 - pair[0].1).abs() <= 1
+            ));
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use bracket_lib::prelude::{BTerm, RGB, WHITE};
+
+use components::glyph::Colour;
+use interface::{Fragment, Label};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Alignment {
+    Left,
+    Centre,
+    Right,
+}
+
+// A single row of coloured fragments, at most as wide as the width it was wrapped to
+pub struct Line {
+    pub fragments: Vec<Fragment>,
+    pub width: i32,
+}
+
+impl Line {
+    fn new() -> Self {
+        Self {
+            fragments: Vec::new(),
+            width: 0,
+        }
+    }
+
+    fn push(&mut self, character: char, colour: Option<Colour>) {
+        match self.fragments.last_mut() {
+            Some(fragment) if same_colour(fragment.colour, colour) => fragment.text.push(character),
+            _ => self
+                .fragments
+                .push(Fragment::new(character.to_string(), colour)),
+        }
+
+        self.width += 1;
+    }
+
+    fn trim_end(&mut self) {
+        while let Some(fragment) = self.fragments.last_mut() {
+            let trimmed = fragment.text.trim_end().to_string();
+
+            self.width -= (fragment.text.chars().count() - trimmed.chars().count()) as i32;
+
+            if trimmed.is_empty() {
+                self.fragments.pop();
+            } else {
+                fragment.text = trimmed;
+                break;
+            }
+        }
+    }
+}
+
+fn same_colour(a: Option<Colour>, b: Option<Colour>) -> bool {
+    a.map(|colour| colour.rgba) == b.map(|colour| colour.rgba)
+}
+
+// Word-wrap a label into lines no wider than the given width
+pub fn wrap(label: &Label, width: i32) -> Vec<Line> {
+    let width = width.max(1);
+    let characters = label
+        .fragments
+        .iter()
+        .flat_map(|fragment| {
+            fragment
+                .text
+                .chars()
+                .map(move |character| (character, fragment.colour))
+        })
+        .collect::<Vec<_>>();
+
+    let mut lines = Vec::new();
+    let mut line = Line::new();
+    let mut index = 0;
+
+    while index < characters.len() {
+        let (character, colour) = characters[index];
+
+        if character == '\n' {
+            line.trim_end();
+            lines.push(line);
+            line = Line::new();
+            index += 1;
+        } else if character.is_whitespace() {
+            // Spaces are dropped at the start of a wrapped line
+            if line.width > 0 && line.width < width {
+                line.push(' ', colour);
+            }
+            index += 1;
+        } else {
+            let word_length = characters[index..]
+                .iter()
+                .take_while(|(c, _)| !c.is_whitespace())
+                .count() as i32;
+
+            // Move the word onto its own line, unless it would not fit on any line
+            if line.width + word_length > width && line.width > 0 {
+                line.trim_end();
+                lines.push(line);
+                line = Line::new();
+            }
+
+            for &(character, colour) in &characters[index..index + word_length as usize] {
+                if line.width == width {
+                    lines.push(line);
+                    line = Line::new();
+                }
+
+                line.push(character, colour);
+            }
+
+            index += word_length as usize;
+        }
+    }
+
+    line.trim_end();
+    if line.width > 0 || lines.is_empty() {
+        lines.push(line);
+    }
+
+    lines
+}
+
+// The number of rows a label occupies when wrapped to the given width
+pub fn rows(label: &Label, width: i32) -> i32 {
+    wrap(label, width).len() as i32
+}
+
+// Print a line within a row of the given width, clipping anything past its end
+pub fn print_line<C: Into<RGB> + Copy>(
+    ctx: &mut BTerm,
+    line: &Line,
+    position: (i32, i32),
+    width: i32,
+    alignment: Alignment,
+    background: C,
+) {
+    let offset = match alignment {
+        Alignment::Left => 0,
+        Alignment::Centre => (width - line.width).max(0) / 2,
+        Alignment::Right => (width - line.width).max(0),
+    };
+    let mut x = position.0 + offset;
+
+    for fragment in &line.fragments {
+        let available = (position.0 + width - x).max(0) as usize;
+        let text = fragment.text.chars().take(available).collect::<String>();
+
+        match fragment.colour {
+            None => ctx.print_color(x, position.1, RGB::named(WHITE), background.into(), &text),
+            Some(colour) => ctx.print_color(x, position.1, colour.rgba, background.into(), &text),
+        }
+
+        x += text.chars().count() as i32;
+    }
+}
+
+// The lines that fit within a height, from the top
+fn clip(lines: &[Line], height: i32) -> &[Line] {
+    &lines[..lines.len().min(height.max(0) as usize)]
+}
+
+// Print lines top to bottom within a rectangle, returning the number of rows used
+pub fn print_lines<C: Into<RGB> + Copy>(
+    ctx: &mut BTerm,
+    lines: &[Line],
+    position: (i32, i32),
+    scale: (i32, i32),
+    alignment: Alignment,
+    background: C,
+) -> i32 {
+    let lines = clip(lines, scale.1);
+
+    for (row, line) in lines.iter().enumerate() {
+        print_line(
+            ctx,
+            line,
+            (position.0, position.1 + row as i32),
+            scale.0,
+            alignment,
+            background,
+        );
+    }
+
+    lines.len() as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bracket_lib::prelude::RGBA;
+    use label;
+
+    fn texts(lines: &[Line]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| {
+                line.fragments
+                    .iter()
+                    .map(|fragment| fragment.text.as_str())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn over_long_words_are_broken_across_lines() {
+        let lines = wrap(&Label::from("a unbreakable word"), 4);
+
+        assert_eq!(texts(&lines), vec!["a", "unbr", "eaka", "ble", "word"]);
+        assert!(lines.iter().all(|line| line.width <= 4));
+    }
+
+    #[test]
+    fn lines_of_exactly_the_width_are_kept_whole() {
+        let lines = wrap(&Label::from("abcd efgh"), 4);
+
+        assert_eq!(texts(&lines), vec!["abcd", "efgh"]);
+        assert_eq!(lines[0].width, 4);
+
+        assert_eq!(texts(&wrap(&Label::from("ab cd"), 5)), vec!["ab cd"]);
+    }
+
+    #[test]
+    fn empty_input_is_one_empty_line() {
+        for label in [Label::new(), Label::from(""), Label::from("   ")].iter() {
+            let lines = wrap(label, 10);
+
+            assert_eq!(lines.len(), 1);
+            assert_eq!(lines[0].width, 0);
+            assert!(lines[0].fragments.is_empty());
+        }
+
+        assert_eq!(rows(&Label::new(), 10), 1);
+    }
+
+    #[test]
+    fn colours_are_kept_across_wrapped_lines() {
+        let red = Colour::from(RGBA::from_u8(255, 0, 0, 255));
+        let lines = wrap(&label!["the ", ("red", red), " gem"], 7);
+
+        assert_eq!(texts(&lines), vec!["the red", "gem"]);
+        assert!(same_colour(lines[0].fragments[1].colour, Some(red)));
+        assert!(same_colour(lines[1].fragments[0].colour, None));
+    }
+
+    #[test]
+    fn clipping_keeps_the_lines_that_fit() {
+        let lines = wrap(&Label::from("one two three four"), 5);
+
+        assert_eq!(texts(clip(&lines, 2)), vec!["one", "two"]);
+        assert_eq!(clip(&lines, 10).len(), lines.len());
+        assert!(clip(&lines, 0).is_empty());
+        assert!(clip(&lines, -3).is_empty());
+    }
+}
@@ -0,0 +1,379 @@
+use bracket_lib::prelude::{BTerm, RGB};
+
+use interface::layout::{self, Alignment, Line};
+use interface::screen::Rect;
+use interface::{Element, Fragment, Label};
+use std::collections::VecDeque;
+
+// The kind of event a message describes
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Category {
+    Combat,
+    Item,
+    World,
+    System,
+}
+
+impl Category {
+    pub fn all() -> [Category; 4] {
+        [
+            Category::Combat,
+            Category::Item,
+            Category::World,
+            Category::System,
+        ]
+    }
+}
+
+// A logged label, stamped with the turn it was last logged on
+pub struct Message {
+    pub label: Label,
+    pub category: Category,
+    pub turn: u64,
+    pub count: u32,
+}
+
+impl Message {
+    // The message as it is displayed, with its turn stamp and repetition count
+    pub fn display(&self) -> Label {
+        let dim = RGB::from((90, 90, 110));
+        let mut label = Label::new();
+
+        label.fragments.push(Fragment::new(
+            format!("{:>5} ", self.turn),
+            Some(dim.into()),
+        ));
+        label.fragments.extend(self.label.fragments.iter().cloned());
+
+        if self.count > 1 {
+            label
+                .fragments
+                .push(Fragment::new(format!(" x{}", self.count), Some(dim.into())));
+        }
+
+        label
+    }
+}
+
+// A scrolling message log that holds a limited number of messages
+pub struct Log {
+    position: (i32, i32),
+    scale: (i32, i32),
+    messages: VecDeque<Message>,
+    capacity: usize,
+    scroll: usize,
+    filter: Option<Category>,
+    history: bool,
+    history_bounds: Rect,
+}
+
+impl Log {
+    pub fn new(position: (i32, i32), scale: (i32, i32), capacity: usize) -> Self {
+        Self {
+            position,
+            scale,
+            messages: VecDeque::with_capacity(capacity),
+            capacity,
+            scroll: 0,
+            filter: None,
+            history: false,
+            history_bounds: Rect::new(position, scale),
+        }
+    }
+
+    // Log a message, folding it into the previous message if they are the same
+    pub fn add(&mut self, turn: u64, category: Category, label: Label) {
+        if let Some(last) = self.messages.back_mut() {
+            if last.category == category && last.label.text() == label.text() {
+                last.count += 1;
+                last.turn = turn;
+
+                return;
+            }
+        }
+
+        if self.messages.len() == self.capacity {
+            self.messages.pop_front();
+        }
+
+        self.messages.push_back(Message {
+            label,
+            category,
+            turn,
+            count: 1,
+        });
+    }
+
+    pub fn messages(&self) -> impl DoubleEndedIterator<Item = &Message> {
+        let filter = self.filter;
+
+        self.messages
+            .iter()
+            .filter(move |message| filter.is_none_or(|category| message.category == category))
+    }
+
+    // Scroll back through older messages, by rows
+    pub fn scroll_up(&mut self, rows: usize) {
+        let (width, height) = self.inner_scale();
+        let total = self
+            .messages()
+            .map(|message| layout::rows(&message.display(), width) as usize)
+            .sum::<usize>();
+
+        self.scroll = (self.scroll + rows).min(total.saturating_sub(height as usize));
+    }
+
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.scroll = self.scroll.saturating_sub(lines);
+    }
+
+    // Cycle between showing all messages and showing each category alone
+    pub fn cycle_filter(&mut self) {
+        let categories = Category::all();
+
+        self.filter = match self.filter {
+            None => Some(categories[0]),
+            Some(category) => categories
+                .iter()
+                .position(|c| *c == category)
+                .and_then(|index| categories.get(index + 1))
+                .cloned(),
+        };
+        self.scroll = 0;
+    }
+
+    // Toggle between the log pane and the full-screen message history
+    pub fn toggle_history(&mut self) {
+        self.history = !self.history;
+        self.scroll = 0;
+    }
+
+    pub fn is_history(&self) -> bool {
+        self.history
+    }
+
+    // Move the pane, and size the full-screen history to the given screen bounds
+    pub fn resize(&mut self, pane: Rect, screen: Rect) {
+        self.position = pane.position;
+        self.scale = pane.scale;
+        self.history_bounds = screen;
+    }
+
+    fn bounds(&self) -> ((i32, i32), (i32, i32)) {
+        if self.history {
+            (self.history_bounds.position, self.history_bounds.scale)
+        } else {
+            (self.position, self.scale)
+        }
+    }
+
+    fn inner_scale(&self) -> (i32, i32) {
+        let (_, scale) = self.bounds();
+
+        (scale.0 - 2, scale.1 - 2)
+    }
+
+    // The wrapped rows of the newest messages, oldest first, enough to fill the given height
+    fn lines(&self, width: i32, height: i32) -> Vec<Line> {
+        let wanted = height.max(0) as usize + self.scroll;
+        let mut lines = Vec::new();
+
+        for message in self.messages().rev() {
+            if lines.len() >= wanted {
+                break;
+            }
+
+            let mut message_lines = layout::wrap(&message.display(), width);
+            message_lines.extend(lines);
+            lines = message_lines;
+        }
+
+        let end = lines.len().saturating_sub(self.scroll);
+        let start = end.saturating_sub(height.max(0) as usize);
+
+        lines.drain(start..end).collect()
+    }
+}
+
+impl Element for Log {
+    fn render(&self, ctx: &mut BTerm) {
+        let (position, scale) = self.bounds();
+        let (width, height) = self.inner_scale();
+
+        ctx.draw_box(
+            position.0,
+            position.1,
+            scale.0 - 1,
+            scale.1 - 1,
+            self.foreground_colour(),
+            self.background_colour(),
+        );
+
+        let title = match self.filter {
+            None => String::from(" Log "),
+            Some(category) => format!(" Log ({:?}) ", category),
+        };
+        ctx.print(position.0 + 2, position.1, title);
+
+        let lines = self.lines(width, height);
+        let top = position.1 + 1 + height - lines.len() as i32;
+
+        layout::print_lines(
+            ctx,
+            &lines,
+            (position.0 + 1, top),
+            (width, height),
+            Alignment::Left,
+            self.background_colour(),
+        );
+
+        if self.scroll > 0 {
+            let more = layout::wrap(&Label::from(format!(" -{} more ", self.scroll)), width);
+
+            layout::print_line(
+                ctx,
+                &more[0],
+                (position.0 + 1, position.1 + scale.1 - 1),
+                width,
+                Alignment::Right,
+                self.background_colour(),
+            );
+        }
+    }
+
+    fn position(&self) -> (i32, i32) {
+        self.position
+    }
+    fn scale(&self) -> (i32, i32) {
+        self.scale
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(log: &Log) -> Vec<(String, u32)> {
+        log.messages()
+            .map(|message| (message.label.text(), message.count))
+            .collect()
+    }
+
+    #[test]
+    fn repeated_messages_are_merged() {
+        let mut log = Log::new((0, 0), (40, 10), 10);
+
+        log.add(1, Category::World, Label::from("You hear a clatter"));
+        log.add(2, Category::World, Label::from("You hear a clatter"));
+        log.add(3, Category::World, Label::from("You hear a clatter"));
+
+        assert_eq!(texts(&log), vec![(String::from("You hear a clatter"), 3)]);
+        assert_eq!(log.messages().next().unwrap().turn, 3);
+        assert_eq!(
+            log.messages().next().unwrap().display().text(),
+            "    3 You hear a clatter x3"
+        );
+
+        // Only back to back repeats of the same category are merged
+        log.add(4, Category::Item, Label::from("You hear a clatter"));
+        log.add(5, Category::World, Label::from("You hear a clatter"));
+
+        assert_eq!(
+            texts(&log)
+                .into_iter()
+                .map(|(_, count)| count)
+                .collect::<Vec<_>>(),
+            vec![3, 1, 1]
+        );
+    }
+
+    #[test]
+    fn the_oldest_messages_make_way_at_capacity() {
+        let mut log = Log::new((0, 0), (40, 10), 3);
+
+        for turn in 0..5 {
+            log.add(turn, Category::World, Label::from(turn.to_string()));
+        }
+
+        assert_eq!(
+            texts(&log)
+                .into_iter()
+                .map(|(text, _)| text)
+                .collect::<Vec<_>>(),
+            vec!["2", "3", "4"]
+        );
+    }
+
+    #[test]
+    fn filtering_shows_one_category_at_a_time() {
+        let mut log = Log::new((0, 0), (40, 10), 10);
+
+        log.add(1, Category::Combat, Label::from("hit"));
+        log.add(2, Category::Item, Label::from("taken"));
+        log.add(3, Category::World, Label::from("heard"));
+        log.add(4, Category::Item, Label::from("stowed"));
+
+        let mut shown = Vec::new();
+
+        for _ in 0..=Category::all().len() {
+            log.cycle_filter();
+            shown.push(
+                log.messages()
+                    .map(|message| message.label.text())
+                    .collect::<Vec<_>>(),
+            );
+        }
+
+        // Each category alone, then everything once more
+        assert_eq!(
+            shown,
+            vec![
+                vec!["hit"],
+                vec!["taken", "stowed"],
+                vec!["heard"],
+                vec![],
+                vec!["hit", "taken", "heard", "stowed"],
+            ]
+        );
+    }
+
+    #[test]
+    fn scrolling_stays_within_the_messages() {
+        // Eight rows inside the frame, each message taking one
+        let mut log = Log::new((0, 0), (40, 10), 50);
+
+        log.scroll_up(5);
+        assert_eq!(log.scroll, 0);
+
+        for turn in 0..20 {
+            log.add(
+                turn,
+                Category::World,
+                Label::from(format!("message {}", turn)),
+            );
+        }
+
+        log.scroll_up(5);
+        assert_eq!(log.scroll, 5);
+        assert_eq!(log.lines(38, 8).len(), 8);
+
+        log.scroll_up(100);
+        assert_eq!(log.scroll, 12);
+        assert_eq!(
+            log.lines(38, 8)[0]
+                .fragments
+                .iter()
+                .map(|fragment| fragment.text.as_str())
+                .collect::<String>(),
+            "0 message 0"
+        );
+
+        log.scroll_down(100);
+        assert_eq!(log.scroll, 0);
+
+        // Changing what is shown starts again from the newest message
+        log.scroll_up(3);
+        log.cycle_filter();
+        assert_eq!(log.scroll, 0);
+    }
+}
@@ -0,0 +1,593 @@
+use bracket_lib::prelude::{letter_to_option, BTerm, VirtualKeyCode, RGB};
+
+use interface::layout::{self, Alignment};
+use interface::screen::{Rect, Screen};
+use interface::{Element, Interactive, Label, Response};
+use label;
+
+const HIGHLIGHT_COLOUR: (u8, u8, u8) = (45, 45, 70);
+
+// Options past the letters of the alphabet are left without a shortcut
+const SHORTCUTS: usize = 26;
+
+// Columns kept clear between a modal element and the edge of the screen
+const SCREEN_MARGIN: i32 = 2;
+
+// The rectangle modal elements are centred within
+fn modal_bounds(screen: &Screen) -> Rect {
+    let Rect { position, scale } = screen.bounds;
+
+    Rect::new(
+        (position.0 + SCREEN_MARGIN, position.1 + SCREEN_MARGIN),
+        (scale.0 - SCREEN_MARGIN * 2, scale.1 - SCREEN_MARGIN * 2),
+    )
+}
+
+fn draw_frame<E: Element>(element: &E, ctx: &mut BTerm, title: &str) {
+    let (position, scale) = (element.position(), element.scale());
+
+    ctx.draw_box(
+        position.0,
+        position.1,
+        scale.0 - 1,
+        scale.1 - 1,
+        element.foreground_colour(),
+        element.background_colour(),
+    );
+
+    if !title.is_empty() {
+        ctx.print(position.0 + 2, position.1, format!(" {} ", title));
+    }
+}
+
+// A list of options, chosen with the arrow keys, letter shortcuts or the mouse
+pub struct Menu {
+    title: String,
+    options: Vec<Label>,
+    selected: usize,
+    scroll: usize,             // The first option shown, once there are more than fit
+    mouse: Option<(i32, i32)>, // Where the mouse was last seen, so that only moving it selects
+    position: (i32, i32),
+    scale: (i32, i32),
+}
+
+impl Menu {
+    pub fn new<S: ToString>(title: S, options: Vec<Label>) -> Self {
+        Self {
+            title: title.to_string(),
+            options,
+            selected: 0,
+            scroll: 0,
+            mouse: None,
+            position: (0, 0),
+            scale: (0, 0),
+        }
+    }
+
+    // How many options fit inside the frame at once
+    fn rows(&self) -> usize {
+        (self.scale.1 - 2).max(0) as usize
+    }
+
+    // Scroll just far enough to bring the selected option into view
+    fn follow(&mut self) {
+        let rows = self.rows().max(1);
+
+        if self.selected < self.scroll {
+            self.scroll = self.selected;
+        } else if self.selected >= self.scroll + rows {
+            self.scroll = self.selected + 1 - rows;
+        }
+
+        self.scroll = self.scroll.min(self.options.len().saturating_sub(rows));
+    }
+
+    // The option under the mouse, if any
+    fn hovered(&self, ctx: &BTerm) -> Option<usize> {
+        let (x, y) = ctx.mouse_pos();
+        let row = y - self.position.1 - 1;
+
+        if x > self.position.0
+            && x < self.position.0 + self.scale.0 - 1
+            && row >= 0
+            && (row as usize) < self.rows()
+            && self.scroll + (row as usize) < self.options.len()
+        {
+            Some(self.scroll + row as usize)
+        } else {
+            None
+        }
+    }
+}
+
+impl Element for Menu {
+    fn render(&self, ctx: &mut BTerm) {
+        draw_frame(self, ctx, &self.title);
+
+        let width = self.scale.0 - 2;
+
+        if self.options.is_empty() {
+            let line = &layout::wrap(&label!["(nothing)"], width)[0];
+            layout::print_line(
+                ctx,
+                line,
+                (self.position.0 + 1, self.position.1 + 1),
+                width,
+                Alignment::Centre,
+                self.background_colour(),
+            );
+        }
+
+        // Only the options scrolled into view are drawn
+        for (index, option) in self
+            .options
+            .iter()
+            .enumerate()
+            .skip(self.scroll)
+            .take(self.rows())
+        {
+            let y = self.position.1 + 1 + (index - self.scroll) as i32;
+            let background = if index == self.selected {
+                RGB::from(HIGHLIGHT_COLOUR)
+            } else {
+                RGB::from(self.background_colour())
+            };
+
+            ctx.print_color(
+                self.position.0 + 1,
+                y,
+                self.foreground_colour(),
+                background,
+                format!("{:width$}", "", width = width as usize),
+            );
+
+            let mut entry = if index < SHORTCUTS {
+                Label::from(format!("{}) ", (b'a' + index as u8) as char))
+            } else {
+                Label::from("   ")
+            };
+            entry += option.clone();
+
+            layout::print_line(
+                ctx,
+                &layout::wrap(&entry, width)[0],
+                (self.position.0 + 1, y),
+                width,
+                Alignment::Left,
+                background,
+            );
+        }
+    }
+
+    fn position(&self) -> (i32, i32) {
+        self.position
+    }
+    fn scale(&self) -> (i32, i32) {
+        self.scale
+    }
+}
+
+impl Interactive for Menu {
+    fn fit(&mut self, screen: &Screen) {
+        let width = self
+            .options
+            .iter()
+            .map(|option| option.text().chars().count() as i32 + 3)
+            .chain(Some(self.title.chars().count() as i32 + 2))
+            .max()
+            .unwrap_or(0)
+            + 4;
+        let rect = modal_bounds(screen).centred((width, self.options.len().max(1) as i32 + 2));
+
+        self.position = rect.position;
+        self.scale = rect.scale;
+        self.follow();
+    }
+
+    fn input(&mut self, ctx: &mut BTerm) -> Response {
+        let mouse = ctx.mouse_pos();
+        let moved = self.mouse.is_some_and(|last| last != mouse);
+        self.mouse = Some(mouse);
+
+        if let Some(hovered) = self.hovered(ctx) {
+            if moved {
+                self.selected = hovered;
+            }
+
+            if ctx.left_click {
+                return Response::Selected(hovered);
+            }
+        }
+
+        match ctx.key {
+            Some(VirtualKeyCode::Escape) => Response::Cancelled,
+            Some(VirtualKeyCode::Up) => {
+                if !self.options.is_empty() {
+                    self.selected = (self.selected + self.options.len() - 1) % self.options.len();
+                    self.follow();
+                }
+
+                Response::Pending
+            }
+            Some(VirtualKeyCode::Down) => {
+                if !self.options.is_empty() {
+                    self.selected = (self.selected + 1) % self.options.len();
+                    self.follow();
+                }
+
+                Response::Pending
+            }
+            Some(VirtualKeyCode::Return) if !self.options.is_empty() => {
+                Response::Selected(self.selected)
+            }
+            Some(key) => match letter_to_option(key) {
+                index if index >= 0 && (index as usize) < self.options.len().min(SHORTCUTS) => {
+                    Response::Selected(index as usize)
+                }
+                _ => Response::Pending,
+            },
+            None => Response::Pending,
+        }
+    }
+}
+
+// A yes or no question
+pub struct Prompt {
+    question: Label,
+    position: (i32, i32),
+    scale: (i32, i32),
+}
+
+impl Prompt {
+    pub fn new(question: Label) -> Self {
+        Self {
+            question,
+            position: (0, 0),
+            scale: (0, 0),
+        }
+    }
+
+    fn question(&self) -> Label {
+        let mut question = self.question.clone();
+        question += label![("  [y/n]", RGB::from((150, 150, 170)))];
+
+        question
+    }
+}
+
+impl Element for Prompt {
+    fn render(&self, ctx: &mut BTerm) {
+        draw_frame(self, ctx, "");
+
+        let width = self.scale.0 - 2;
+
+        layout::print_lines(
+            ctx,
+            &layout::wrap(&self.question(), width),
+            (self.position.0 + 1, self.position.1 + 1),
+            (width, self.scale.1 - 2),
+            Alignment::Left,
+            self.background_colour(),
+        );
+    }
+
+    fn position(&self) -> (i32, i32) {
+        self.position
+    }
+    fn scale(&self) -> (i32, i32) {
+        self.scale
+    }
+}
+
+impl Interactive for Prompt {
+    fn fit(&mut self, screen: &Screen) {
+        let bounds = modal_bounds(screen);
+        let question = self.question();
+        let width = (question.text().chars().count() as i32).min(bounds.scale.0 - 2);
+        let rect = bounds.centred((width + 2, layout::rows(&question, width) + 2));
+
+        self.position = rect.position;
+        self.scale = rect.scale;
+    }
+
+    fn input(&mut self, ctx: &mut BTerm) -> Response {
+        match ctx.key {
+            Some(VirtualKeyCode::Y) => Response::Confirmed(true),
+            Some(VirtualKeyCode::N) => Response::Confirmed(false),
+            Some(VirtualKeyCode::Escape) => Response::Cancelled,
+            _ => Response::Pending,
+        }
+    }
+}
+
+// Pick a number within a range, with the arrow keys or by typing it
+pub struct QuantityPicker {
+    title: String,
+    quantity: u32,
+    minimum: u32,
+    maximum: u32,
+    typed: bool,
+    position: (i32, i32),
+    scale: (i32, i32),
+}
+
+impl QuantityPicker {
+    pub fn new<S: ToString>(title: S, minimum: u32, maximum: u32) -> Self {
+        Self {
+            title: title.to_string(),
+            quantity: maximum,
+            minimum,
+            maximum,
+            typed: false,
+            position: (0, 0),
+            scale: (0, 0),
+        }
+    }
+
+    fn adjust(&mut self, amount: i64) {
+        self.quantity = (self.quantity as i64 + amount)
+            .max(self.minimum as i64)
+            .min(self.maximum as i64) as u32;
+        self.typed = false;
+    }
+
+    fn type_digit(&mut self, digit: u32) {
+        let quantity = if self.typed {
+            self.quantity.saturating_mul(10).saturating_add(digit)
+        } else {
+            digit
+        };
+
+        self.quantity = quantity.min(self.maximum);
+        self.typed = true;
+    }
+}
+
+impl Element for QuantityPicker {
+    fn render(&self, ctx: &mut BTerm) {
+        draw_frame(self, ctx, &self.title);
+
+        let width = self.scale.0 - 2;
+        let line = format!("< {} / {} >", self.quantity, self.maximum);
+
+        layout::print_line(
+            ctx,
+            &layout::wrap(&Label::from(line), width)[0],
+            (self.position.0 + 1, self.position.1 + 1),
+            width,
+            Alignment::Centre,
+            self.background_colour(),
+        );
+    }
+
+    fn position(&self) -> (i32, i32) {
+        self.position
+    }
+    fn scale(&self) -> (i32, i32) {
+        self.scale
+    }
+}
+
+impl Interactive for QuantityPicker {
+    fn fit(&mut self, screen: &Screen) {
+        let width = self.title.chars().count().max(16) as i32 + 6;
+        let rect = modal_bounds(screen).centred((width, 3));
+
+        self.position = rect.position;
+        self.scale = rect.scale;
+    }
+
+    fn input(&mut self, ctx: &mut BTerm) -> Response {
+        let digit = match ctx.key {
+            Some(VirtualKeyCode::Key0) => Some(0),
+            Some(VirtualKeyCode::Key1) => Some(1),
+            Some(VirtualKeyCode::Key2) => Some(2),
+            Some(VirtualKeyCode::Key3) => Some(3),
+            Some(VirtualKeyCode::Key4) => Some(4),
+            Some(VirtualKeyCode::Key5) => Some(5),
+            Some(VirtualKeyCode::Key6) => Some(6),
+            Some(VirtualKeyCode::Key7) => Some(7),
+            Some(VirtualKeyCode::Key8) => Some(8),
+            Some(VirtualKeyCode::Key9) => Some(9),
+            _ => None,
+        };
+
+        if let Some(digit) = digit {
+            self.type_digit(digit);
+
+            return Response::Pending;
+        }
+
+        match ctx.key {
+            Some(VirtualKeyCode::Left) => self.adjust(-1),
+            Some(VirtualKeyCode::Right) => self.adjust(1),
+            Some(VirtualKeyCode::Down) => self.adjust(-10),
+            Some(VirtualKeyCode::Up) => self.adjust(10),
+            Some(VirtualKeyCode::Back) => {
+                self.quantity /= 10;
+            }
+            Some(VirtualKeyCode::Return) if self.quantity >= self.minimum => {
+                return Response::Quantity(self.quantity);
+            }
+            Some(VirtualKeyCode::Escape) => return Response::Cancelled,
+            _ => {}
+        }
+
+        Response::Pending
+    }
+}
+
+// A modal window of text, dismissed with any confirming key
+pub struct Dialog {
+    title: String,
+    body: Label,
+    position: (i32, i32),
+    scale: (i32, i32),
+}
+
+impl Dialog {
+    pub fn new<S: ToString>(title: S, body: Label) -> Self {
+        Self {
+            title: title.to_string(),
+            body,
+            position: (0, 0),
+            scale: (0, 0),
+        }
+    }
+}
+
+impl Element for Dialog {
+    fn render(&self, ctx: &mut BTerm) {
+        draw_frame(self, ctx, &self.title);
+
+        let width = self.scale.0 - 2;
+
+        layout::print_lines(
+            ctx,
+            &layout::wrap(&self.body, width),
+            (self.position.0 + 1, self.position.1 + 1),
+            (width, self.scale.1 - 2),
+            Alignment::Left,
+            self.background_colour(),
+        );
+    }
+
+    fn position(&self) -> (i32, i32) {
+        self.position
+    }
+    fn scale(&self) -> (i32, i32) {
+        self.scale
+    }
+}
+
+impl Interactive for Dialog {
+    fn fit(&mut self, screen: &Screen) {
+        let bounds = modal_bounds(screen);
+        let width = (self.body.text().chars().count() as i32)
+            .max(self.title.chars().count() as i32 + 4)
+            .min(bounds.scale.0 / 2);
+        let rect = bounds.centred((width + 2, layout::rows(&self.body, width) + 2));
+
+        self.position = rect.position;
+        self.scale = rect.scale;
+    }
+
+    fn input(&mut self, ctx: &mut BTerm) -> Response {
+        match ctx.key {
+            Some(VirtualKeyCode::Return)
+            | Some(VirtualKeyCode::Escape)
+            | Some(VirtualKeyCode::Space) => Response::Dismissed,
+            _ if ctx.left_click => Response::Dismissed,
+            _ => Response::Pending,
+        }
+    }
+}
+
+// A cursor moved over the map, for looking at what is there
+pub struct Cursor {
+    position: (i32, i32),
+    bounds: Rect,
+}
+
+impl Cursor {
+    pub fn new(position: (i32, i32)) -> Self {
+        Self {
+            position,
+            bounds: Rect::default(),
+        }
+    }
+}
+
+impl Element for Cursor {
+    fn render(&self, ctx: &mut BTerm) {
+        ctx.set_bg(
+            self.position.0,
+            self.position.1,
+            RGB::from(HIGHLIGHT_COLOUR),
+        );
+    }
+
+    fn position(&self) -> (i32, i32) {
+        self.position
+    }
+    fn scale(&self) -> (i32, i32) {
+        (1, 1)
+    }
+}
+
+impl Interactive for Cursor {
+    fn fit(&mut self, screen: &Screen) {
+        self.bounds = screen.viewport;
+        self.position = self.bounds.clamp(self.position);
+    }
+
+    fn input(&mut self, ctx: &mut BTerm) -> Response {
+        let offset = match ctx.key {
+            Some(VirtualKeyCode::Up) | Some(VirtualKeyCode::W) => (0, -1),
+            Some(VirtualKeyCode::Down) | Some(VirtualKeyCode::S) => (0, 1),
+            Some(VirtualKeyCode::Left) | Some(VirtualKeyCode::A) => (-1, 0),
+            Some(VirtualKeyCode::Right) | Some(VirtualKeyCode::D) => (1, 0),
+            Some(VirtualKeyCode::Escape) | Some(VirtualKeyCode::Return) => {
+                return Response::Cancelled
+            }
+            _ => (0, 0),
+        };
+
+        let position = if ctx.left_click {
+            ctx.mouse_pos()
+        } else {
+            (self.position.0 + offset.0, self.position.1 + offset.1)
+        };
+
+        self.position = self.bounds.clamp(position);
+
+        Response::Pending
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn menu(options: usize, rows: i32) -> Menu {
+        let mut menu = Menu::new(
+            "",
+            (0..options)
+                .map(|index| Label::from(index.to_string()))
+                .collect(),
+        );
+        menu.scale = (20, rows + 2);
+
+        menu
+    }
+
+    #[test]
+    fn scrolling_keeps_the_selection_in_view() {
+        let mut menu = menu(30, 10);
+
+        for selected in (0..30).chain((0..30).rev()) {
+            menu.selected = selected;
+            menu.follow();
+
+            assert!(menu.scroll <= selected && selected < menu.scroll + menu.rows());
+            assert!(menu.scroll + menu.rows() <= 30);
+        }
+
+        // Wrapping around from the top scrolls to the bottom
+        menu.selected = 29;
+        menu.follow();
+        assert_eq!(menu.scroll, 20);
+    }
+
+    #[test]
+    fn menus_that_fit_never_scroll() {
+        let mut menu = menu(5, 10);
+
+        for selected in 0..5 {
+            menu.selected = selected;
+            menu.follow();
+
+            assert_eq!(menu.scroll, 0);
+        }
+    }
+}
@@ -1,9 +1,18 @@
 use bracket_lib::prelude::{BTerm, RGB, WHITE};
 
+use interface::layout::Alignment;
+use interface::screen::{Rect, Screen};
+
 use components::glyph::{Colour, Glyph};
 use std::ops::{Add, AddAssign};
 
+pub mod layout;
+pub mod log;
+pub mod menu;
+pub mod screen;
+
 // A fragment of text within a label
+#[derive(Clone)]
 pub struct Fragment {
     pub text: String,
     pub colour: Option<Colour>,
@@ -83,6 +92,7 @@ impl From<Glyph> for Fragment {
 }
 
 // A label strung together with fragments
+#[derive(Clone)]
 pub struct Label {
     pub fragments: Vec<Fragment>,
 }
@@ -93,6 +103,17 @@ impl Label {
             fragments: Vec::default(),
         }
     }
+
+    // The label's text without colours
+    pub fn text(&self) -> String {
+        self.fragments
+            .iter()
+            .fold(String::new(), |mut string, fragment| {
+                string += fragment.text.as_str();
+
+                string
+            })
+    }
 }
 
 // Label concatenation
@@ -120,6 +141,14 @@ impl From<&str> for Label {
     }
 }
 
+impl From<String> for Label {
+    fn from(string: String) -> Self {
+        Self {
+            fragments: vec![string.into()],
+        }
+    }
+}
+
 pub trait Element {
     fn render(&self, ctx: &mut BTerm);
     fn position(&self) -> (i32, i32);
@@ -132,6 +161,24 @@ pub trait Element {
     }
 }
 
+// The outcome of input given to an interactive element
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Response {
+    Pending,
+    Cancelled,
+    Dismissed,
+    Selected(usize),
+    Confirmed(bool),
+    Quantity(u32),
+}
+
+// An element that takes input focus while it is open
+pub trait Interactive: Element {
+    // Position and size the element for the current screen
+    fn fit(&mut self, _screen: &Screen) {}
+    fn input(&mut self, ctx: &mut BTerm) -> Response;
+}
+
 pub struct List {
     position: (i32, i32),
     scale: (i32, i32),
@@ -154,17 +201,11 @@ impl List {
     }
 
     pub fn remove<T: ToString>(&mut self, element: T) {
-        if let Some(index) = self.entries.iter().position(|label| {
-            label
-                .fragments
-                .iter()
-                .fold(String::new(), |mut string, fragment| {
-                    string += fragment.text.as_str();
-
-                    string
-                })
-                == element.to_string()
-        }) {
+        if let Some(index) = self
+            .entries
+            .iter()
+            .position(|label| label.text() == element.to_string())
+        {
             self.entries.remove(index);
         }
     }
@@ -172,32 +213,43 @@ impl List {
     pub fn clear(&mut self) {
         self.entries.clear();
     }
+
+    pub fn resize(&mut self, rect: Rect) {
+        self.position = rect.position;
+        self.scale = rect.scale;
+    }
+}
+
+impl List {
+    // The inner width available to entries, inside the border
+    fn inner_width(&self) -> i32 {
+        self.scale.0 - 2
+    }
 }
 
 impl Element for List {
     fn render(&self, ctx: &mut BTerm) {
         ctx.draw_box(
-            self.position.0 as i32,
-            self.position.1 as i32,
-            self.scale.0,
-            self.scale.1,
+            self.position.0,
+            self.position.1,
+            self.scale.0 - 1,
+            self.scale.1 - 1,
             self.foreground_colour(),
             self.background_colour(),
         );
 
-        for (index, label) in self.entries.iter().enumerate() {
-            let mut x = self.position.0 + 1;
-            let y = self.position.1 + self.entry_padding + index as i32 + 1;
-            for fragment in &label.fragments {
-                match fragment.colour {
-                    None => ctx.print(x, y, &fragment.text),
-                    Some(colour) => {
-                        ctx.print_color(x, y, colour.rgba, RGB::from((0, 0, 0)), &fragment.text)
-                    }
-                }
-
-                x += fragment.text.len() as i32;
-            }
+        let mut y = self.position.1 + self.entry_padding + 1;
+        let bottom = self.position.1 + self.scale.1 - 1;
+
+        for label in &self.entries {
+            y += layout::print_lines(
+                ctx,
+                &layout::wrap(label, self.inner_width()),
+                (self.position.0 + 1, y),
+                (self.inner_width(), bottom - y),
+                Alignment::Left,
+                RGB::from((0, 0, 0)),
+            );
         }
     }
 
@@ -0,0 +1,103 @@
+// Fraction of the screen given to the side pane and to the log pane
+const INVENTORY_PANE_FRACTION: i32 = 4;
+const EVENT_LOG_PANE_FRACTION: i32 = 4;
+
+// The smallest the panes are allowed to shrink to
+const INVENTORY_PANE_MIN_WIDTH: i32 = 20;
+const EVENT_LOG_PANE_MIN_HEIGHT: i32 = 6;
+
+// A rectangle of console cells
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Rect {
+    pub position: (i32, i32),
+    pub scale: (i32, i32),
+}
+
+impl Rect {
+    pub fn new(position: (i32, i32), scale: (i32, i32)) -> Self {
+        Self {
+            position,
+            scale: (scale.0.max(0), scale.1.max(0)),
+        }
+    }
+
+    pub fn centre(&self) -> (i32, i32) {
+        (
+            self.position.0 + self.scale.0 / 2,
+            self.position.1 + self.scale.1 / 2,
+        )
+    }
+
+    pub fn contains(&self, point: (i32, i32)) -> bool {
+        point.0 >= self.position.0
+            && point.1 >= self.position.1
+            && point.0 < self.position.0 + self.scale.0
+            && point.1 < self.position.1 + self.scale.1
+    }
+
+    // Clamp a point to lie within the rectangle
+    pub fn clamp(&self, point: (i32, i32)) -> (i32, i32) {
+        (
+            point
+                .0
+                .max(self.position.0)
+                .min(self.position.0 + self.scale.0 - 1),
+            point
+                .1
+                .max(self.position.1)
+                .min(self.position.1 + self.scale.1 - 1),
+        )
+    }
+
+    // A rectangle of the given scale centred within this one, shrunk to fit if needed
+    pub fn centred(&self, scale: (i32, i32)) -> Rect {
+        let scale = (scale.0.min(self.scale.0), scale.1.min(self.scale.1));
+        let centre = self.centre();
+
+        Rect::new((centre.0 - scale.0 / 2, centre.1 - scale.1 / 2), scale)
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = (i32, i32)> {
+        let Rect { position, scale } = *self;
+
+        (position.1..position.1 + scale.1)
+            .flat_map(move |y| (position.0..position.0 + scale.0).map(move |x| (x, y)))
+    }
+}
+
+// How the console is divided between the map and the interface panes
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Screen {
+    pub bounds: Rect,
+    pub viewport: Rect,
+    pub inventory_pane: Rect,
+    pub event_log_pane: Rect,
+}
+
+impl Screen {
+    pub fn new(size: (i32, i32)) -> Self {
+        let inventory_width = (size.0 / INVENTORY_PANE_FRACTION)
+            .max(INVENTORY_PANE_MIN_WIDTH)
+            .min(size.0);
+        let event_log_height = (size.1 / EVENT_LOG_PANE_FRACTION)
+            .max(EVENT_LOG_PANE_MIN_HEIGHT)
+            .min(size.1);
+
+        Self {
+            bounds: Rect::new((0, 0), size),
+            viewport: Rect::new(
+                (0, 0),
+                (size.0 - inventory_width, size.1 - event_log_height),
+            ),
+            inventory_pane: Rect::new(
+                (size.0 - inventory_width, 0),
+                (inventory_width, size.1 - event_log_height),
+            ),
+            event_log_pane: Rect::new((0, size.1 - event_log_height), (size.0, event_log_height)),
+        }
+    }
+
+    pub fn size(&self) -> (i32, i32) {
+        self.bounds.scale
+    }
+}
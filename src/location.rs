@@ -3,12 +3,20 @@ use components::position::Position;
 use map::{Map, Object};
 
 use components::barrier::Barrier;
+use components::layer::Layer;
+use components::tile::Tile;
 
+use entities::coating::Coating;
+use entities::creature::Creature;
+use entities::feature::Feature;
 use entities::floor::Floor;
 use entities::wall::Wall;
+use geometry;
 use legion::systems::CommandBuffer;
 use legion::*;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, RwLock};
+use vector::Vector;
 
 pub struct Dungeon<'a> {
     world: &'a mut World,
@@ -57,18 +65,16 @@ impl<'a> Dungeon<'a> {
     }
      */
 
-    pub fn generate_caves(self, enhancement_passes: u32) {
+    pub fn generate_caves(&mut self, enhancement_passes: u32) {
         let mut commands = CommandBuffer::new(self.world);
         let mut rng = RandomNumberGenerator::new();
 
         // Randomly spawn walls
-        for y in 0..self.dimensions.1 {
-            for x in 0..self.dimensions.0 {
-                if rng.rand::<f32>() < 0.35 {
-                    self.wall.spawn(&mut commands, self.map.clone(), (x, y));
-                } else {
-                    self.floor.spawn(&mut commands, self.map.clone(), (x, y));
-                }
+        for position in geometry::rect(Vector(0, 0), self.dimensions.into()) {
+            if rng.rand::<f32>() < 0.35 {
+                self.wall.spawn(&mut commands, self.map.clone(), position);
+            } else {
+                self.floor.spawn(&mut commands, self.map.clone(), position);
             }
         }
 
@@ -78,9 +84,9 @@ impl<'a> Dungeon<'a> {
         for _i in 0..enhancement_passes {
             let mut commands = CommandBuffer::new(self.world);
 
-            for (position, barrier) in <(&Position, Option<&Barrier>)>::query()
+            for (entity, position, barrier) in <(Entity, &Position, Option<&Barrier>)>::query()
                 .iter(self.world)
-                .map(|(p, w)| (*p, w.cloned()))
+                .map(|(e, p, w)| (*e, *p, w.cloned()))
                 .collect::<Vec<_>>()
             {
                 let wall_neighbours: Vec<Object> = self
@@ -107,12 +113,8 @@ impl<'a> Dungeon<'a> {
                 let delete_wall = barrier.is_some() && (wall_neighbours.len() < 2);
 
                 if become_wall || delete_wall {
-                    self.world.remove(
-                        self.map.write().unwrap()[position.vector]
-                            .pop()
-                            .unwrap()
-                            .entity,
-                    );
+                    self.map.write().unwrap().remove(entity);
+                    self.world.remove(entity);
 
                     if become_wall {
                         self.wall
@@ -127,6 +129,160 @@ impl<'a> Dungeon<'a> {
             }
         }
     }
+
+    // Hang doors in narrow passages, each worked by a lever or pressure plate beside it,
+    // and crack some of the walls bordering the caves
+    pub fn furnish(&mut self, door: &Feature, lever: &Feature, plate: &Feature, cracked: &Wall) {
+        let mut commands = CommandBuffer::new(self.world);
+        let mut rng = RandomNumberGenerator::new();
+
+        let floors = <(&Position, &Tile)>::query()
+            .iter(self.world)
+            .map(|(position, _)| position.vector)
+            .collect::<HashSet<_>>();
+        let walls = <(Entity, &Position, &Barrier)>::query()
+            .iter(self.world)
+            .map(|(entity, position, _)| (position.vector, *entity))
+            .collect::<HashMap<_, _>>();
+
+        let mut doors: Vec<Vector<i32>> = Vec::new();
+        let mut candidates = floors.iter().cloned().collect::<Vec<_>>();
+        candidates.sort_by_key(|position| (position.1, position.0));
+
+        for position in candidates {
+            // A chokepoint is walled in on one axis and open on the other. Its surroundings are
+            // looked at as a region centred on it, so candidates on the edge of the map are skipped
+            let open_axis = {
+                let map = self.map.read().unwrap();
+                let around = match map.region(position - Vector(1, 1), Vector(3, 3)) {
+                    Some(around) => around,
+                    None => continue,
+                };
+                let is_wall = |offset: Vector<i32>| around.blocks_movement(Vector(1, 1) + offset);
+
+                if is_wall(Vector(0, -1))
+                    && is_wall(Vector(0, 1))
+                    && !is_wall(Vector(-1, 0))
+                    && !is_wall(Vector(1, 0))
+                {
+                    Vector(1, 0)
+                } else if is_wall(Vector(-1, 0))
+                    && is_wall(Vector(1, 0))
+                    && !is_wall(Vector(0, -1))
+                    && !is_wall(Vector(0, 1))
+                {
+                    Vector(0, 1)
+                } else {
+                    continue;
+                }
+            };
+
+            let crowded = doors.iter().any(|other| {
+                let offset = *other - position;
+
+                offset.0.abs() <= 2 && offset.1.abs() <= 2
+            });
+
+            if crowded || rng.rand::<f32>() >= 0.3 {
+                continue;
+            }
+
+            let door_entity = door.spawn(&mut commands, self.map.clone(), position);
+            doors.push(position);
+
+            // Some doors are locked, leaving their trigger or a steady hand to open them
+            if let Some(mut locked) = door.door.filter(|_| rng.rand::<f32>() < 0.3) {
+                locked.locked = true;
+                commands.add_component(door_entity, locked);
+            }
+
+            // The trigger goes on one side of the doorway, out of the way of other doors
+            let side = if rng.rand::<bool>() { 1 } else { -1 };
+            let trigger_position = position + open_axis * side;
+
+            if doors.contains(&trigger_position) {
+                continue;
+            }
+
+            let trigger = if rng.rand::<bool>() { lever } else { plate };
+
+            if let Some(mut linked) = trigger.trigger.clone() {
+                let trigger_entity =
+                    trigger.spawn(&mut commands, self.map.clone(), trigger_position);
+
+                linked.targets.push(door_entity);
+                commands.add_component(trigger_entity, linked);
+            }
+        }
+
+        // Crack a few of the walls that can be reached from the caves
+        for (position, entity) in walls {
+            let exposed = geometry::rect(position - Vector(1, 1), Vector(3, 3))
+                .any(|at| floors.contains(&at));
+
+            if exposed && rng.rand::<f32>() < 0.05 {
+                self.map.write().unwrap().remove(entity);
+                commands.remove(entity);
+                cracked.spawn(&mut commands, self.map.clone(), position);
+            }
+        }
+
+        commands.flush(self.world);
+    }
+
+    // Lay a coating on bare floors here and there
+    pub fn scatter(&mut self, coating: &Coating, chance: f32) {
+        let mut commands = CommandBuffer::new(self.world);
+        let mut rng = RandomNumberGenerator::new();
+
+        let floors = <(&Position, &Tile)>::query()
+            .iter(self.world)
+            .map(|(position, _)| position.vector)
+            .collect::<Vec<_>>();
+
+        for position in floors {
+            let bare = self
+                .map
+                .read()
+                .unwrap()
+                .top(position)
+                .is_some_and(|obj| obj.layer == Layer::Terrain);
+
+            if bare && rng.rand::<f32>() < chance {
+                coating.spawn(&mut commands, self.map.clone(), position);
+            }
+        }
+
+        commands.flush(self.world);
+    }
+
+    // Settle a number of creatures on bare floors picked at random
+    pub fn settle(&mut self, creature: &Creature, count: usize) {
+        let mut commands = CommandBuffer::new(self.world);
+        let mut rng = RandomNumberGenerator::new();
+
+        let mut floors = {
+            let map = self.map.read().unwrap();
+
+            <(&Position, &Tile)>::query()
+                .iter(self.world)
+                .map(|(position, _)| position.vector)
+                .filter(|position| {
+                    map.top(*position)
+                        .is_some_and(|obj| obj.layer == Layer::Terrain)
+                })
+                .collect::<Vec<_>>()
+        };
+
+        for _ in 0..count.min(floors.len()) {
+            let index = rng.range(0, floors.len());
+            let position = floors.swap_remove(index);
+
+            creature.spawn(&mut commands, self.map.clone(), position);
+        }
+
+        commands.flush(self.world);
+    }
 }
 
 pub struct Room {
@@ -16,13 +16,24 @@ use std::fs::File;
 use std::io::BufReader;
 use std::path::Path;
 
+use appearance::Appearance;
+use components::template::Template;
+use entities::coating::Coating;
+use entities::feature::Feature;
 use entities::floor::Floor;
+use entities::hazard::Hazard;
 use entities::wall::Wall;
+use recipe::Recipe;
 
+mod appearance;
+mod camera;
+mod clock;
 mod components;
 mod entities;
 mod game;
+mod geometry;
 mod map;
+mod recipe;
 mod systems;
 mod vector;
 
@@ -32,15 +43,21 @@ mod location;
 
 const WALLS_FILE_PATH: &str = "assets/walls.json";
 const FLOORS_FILE_PATH: &str = "assets/floors.json";
+const FEATURES_FILE_PATH: &str = "assets/features.json";
+const COATINGS_FILE_PATH: &str = "assets/coatings.json";
+const HAZARDS_FILE_PATH: &str = "assets/hazards.json";
 const OBJECTS_FILE_PATH: &str = "assets/objects.json";
+const CREATURES_FILE_PATH: &str = "assets/creatures.json";
+const RECIPES_FILE_PATH: &str = "assets/recipes.json";
+const APPEARANCES_FILE_PATH: &str = "assets/appearances.json";
+const CHUNKS_DIRECTORY_PATH: &str = "saves/chunks";
 
 pub const WINDOW_TITLE: &str = "Protogue";
 pub const WINDOW_DIMENSIONS: (i32, i32) = (100, 50);
-pub const WINDOW_CENTER: (i32, i32) = (WINDOW_DIMENSIONS.0 / 2 - 1, WINDOW_DIMENSIONS.1 / 2 - 1);
 pub const WINDOW_FULLSCREEN: bool = false;
 
-pub const GUI_WIDTH: i32 = WINDOW_DIMENSIONS.0 / 4;
 pub const MAP_DIMENSIONS: (i32, i32) = (250, 250);
+pub const FOV_RADIUS: i32 = 10;
 
 // Create game objects (Walls, floors, items, etc)
 lazy_static! {
@@ -52,14 +69,62 @@ lazy_static! {
         File::open(Path::new(FLOORS_FILE_PATH)).expect("Failed to load floors")
     ))
     .unwrap();
-    static ref ITEMS: HashMap<String, entities::item::Item> = serde_json::from_reader(
-        BufReader::new(File::open(Path::new(OBJECTS_FILE_PATH)).expect("Failed to load items"))
+    static ref FEATURES: HashMap<String, Feature> = serde_json::from_reader(BufReader::new(
+        File::open(Path::new(FEATURES_FILE_PATH)).expect("Failed to load features")
+    ))
+    .unwrap();
+    // Each coating knows its own name, so that it can spread and be tracked elsewhere
+    static ref COATINGS: HashMap<String, Coating> = serde_json::from_reader::<_, HashMap<String, Coating>>(
+        BufReader::new(File::open(Path::new(COATINGS_FILE_PATH)).expect("Failed to load coatings"))
     )
+    .unwrap()
+    .into_iter()
+    .map(|(name, mut coating)| {
+        coating.surface.kind = name.clone();
+        (name, coating)
+    })
+    .collect();
+    static ref HAZARDS: HashMap<String, Hazard> = serde_json::from_reader::<_, HashMap<String, Hazard>>(
+        BufReader::new(File::open(Path::new(HAZARDS_FILE_PATH)).expect("Failed to load hazards"))
+    )
+    .unwrap()
+    .into_iter()
+    .map(|(name, mut hazard)| {
+        if let Some(gas) = hazard.gas.as_mut() {
+            gas.kind = name.clone();
+        }
+        (name, hazard)
+    })
+    .collect();
+    static ref APPEARANCES: HashMap<String, Vec<Appearance>> = serde_json::from_reader(BufReader::new(
+        File::open(Path::new(APPEARANCES_FILE_PATH)).expect("Failed to load appearances")
+    ))
     .unwrap();
+    // Each item remembers the template it came from, so that recipes can ask for it by name, and
+    // unidentified items are given this run's appearances
+    static ref ITEMS: HashMap<String, entities::item::Item> = {
+        let mut items = serde_json::from_reader::<_, HashMap<String, entities::item::Item>>(
+            BufReader::new(File::open(Path::new(OBJECTS_FILE_PATH)).expect("Failed to load items"))
+        )
+        .unwrap()
+        .into_iter()
+        .map(|(name, mut item)| {
+            item.template = Some(Template { name: name.clone() });
+            item.infer_tags();
+            (name, item)
+        })
+        .collect();
+        appearance::disguise(&mut items, &APPEARANCES);
+        items
+    };
     static ref CREATURES: HashMap<String, entities::creature::Creature> = serde_json::from_reader(
-        BufReader::new(File::open(Path::new(OBJECTS_FILE_PATH)).expect("Failed to load creatures"))
+        BufReader::new(File::open(Path::new(CREATURES_FILE_PATH)).expect("Failed to load creatures"))
     )
     .unwrap();
+    static ref RECIPES: HashMap<String, Recipe> = serde_json::from_reader(BufReader::new(
+        File::open(Path::new(RECIPES_FILE_PATH)).expect("Failed to load recipes")
+    ))
+    .unwrap();
 }
 
 fn main() {
@@ -68,6 +133,7 @@ fn main() {
         .unwrap()
         .with_tile_dimensions(16, 16)
         .with_title("Roguelike")
+        .with_automatic_console_resize(true)
         .build()
         .unwrap();
     let g = game::Game::new(MAP_DIMENSIONS);
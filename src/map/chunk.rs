@@ -0,0 +1,98 @@
+use vector::Vector;
+
+// The width and height of a chunk, in tiles
+pub const CHUNK_SIZE: i32 = 32;
+
+// The chunk a position lies in, in chunk coordinates
+pub fn chunk_of(position: Vector<i32>) -> Vector<i32> {
+    Vector(
+        position.0.div_euclid(CHUNK_SIZE),
+        position.1.div_euclid(CHUNK_SIZE),
+    )
+}
+
+// The tile position of a chunk's top-left corner
+pub fn origin_of(chunk: Vector<i32>) -> Vector<i32> {
+    chunk * CHUNK_SIZE
+}
+
+// A square block of stacks, allocated as a whole once anything is placed within it.
+// Chunks on the right and bottom edges of the map are clipped to the map's size
+#[derive(Debug)]
+pub struct Chunk<C> {
+    pub origin: Vector<i32>,
+    pub size: Vector<i32>,
+    pub stacks: Vec<C>,
+}
+
+impl<C> Chunk<C> {
+    // An empty chunk, to be filled with stacks in row order
+    pub fn new(chunk: Vector<i32>, map_size: Vector<i32>) -> Self {
+        let origin = origin_of(chunk);
+        let size = Vector(
+            CHUNK_SIZE.min(map_size.0 - origin.0),
+            CHUNK_SIZE.min(map_size.1 - origin.1),
+        );
+
+        Self {
+            origin,
+            size,
+            stacks: Vec::new(),
+        }
+    }
+
+    pub fn filled(chunk: Vector<i32>, map_size: Vector<i32>) -> Self
+    where
+        C: Default,
+    {
+        let mut filled = Self::new(chunk, map_size);
+        let area = filled.area();
+
+        filled.stacks.resize_with(area, C::default);
+        filled
+    }
+
+    fn area(&self) -> usize {
+        (self.size.0 * self.size.1).max(0) as usize
+    }
+
+    fn index_of(&self, position: Vector<i32>) -> usize {
+        let local = position - self.origin;
+
+        (local.0 + local.1 * self.size.0) as usize
+    }
+
+    pub fn get(&self, position: Vector<i32>) -> Option<&C> {
+        self.stacks.get(self.index_of(position))
+    }
+
+    pub fn get_mut(&mut self, position: Vector<i32>) -> Option<&mut C> {
+        let index = self.index_of(position);
+
+        self.stacks.get_mut(index)
+    }
+
+    // Each stack along with its position on the map
+    pub fn iter(&self) -> impl Iterator<Item = (Vector<i32>, &C)> {
+        let (origin, width) = (self.origin, self.size.0);
+
+        self.stacks.iter().enumerate().map(move |(index, stack)| {
+            let index = index as i32;
+
+            (origin + Vector(index % width, index / width), stack)
+        })
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Vector<i32>, &mut C)> {
+        let (origin, width) = (self.origin, self.size.0);
+
+        self.stacks
+            .iter_mut()
+            .enumerate()
+            .map(move |(index, stack)| {
+                let index = index as i32;
+
+                (origin + Vector(index % width, index / width), stack)
+            })
+    }
+}
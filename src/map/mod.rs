@@ -0,0 +1,842 @@
+use bracket_lib::prelude::*;
+
+pub mod chunk;
+pub mod streaming;
+
+use components::position::Position;
+
+use vector::Vector;
+
+use components::barrier::Barrier;
+use components::direction::Direction;
+use components::layer::Layer;
+use components::opaque::Opaque;
+use legion::systems::CommandBuffer;
+use legion::world::SubWorld;
+use legion::*;
+
+use entities::floor::Floor;
+use entities::wall::Wall;
+use geometry;
+use map::chunk::{chunk_of, Chunk};
+use std::collections::HashMap;
+use std::ops::{Index, IndexMut};
+use std::sync::{Arc, RwLock};
+
+// A generic object on the map (used for computing FOV and collision detection)
+#[derive(Debug, Clone)]
+pub struct Object {
+    pub entity: Entity,
+    pub layer: Layer,
+    pub block_fov: bool,
+    pub block_movement: bool,
+}
+
+impl Object {
+    pub fn new(entity: Entity, layer: Layer, block_fov: bool, block_movement: bool) -> Self {
+        Self {
+            entity,
+            layer,
+            block_fov,
+            block_movement,
+        }
+    }
+
+    pub fn from_entity(entity: Entity, subworld: &SubWorld) -> Self {
+        let entry = subworld.entry_ref(entity).unwrap();
+
+        Self::from_components(
+            entity,
+            entry.get_component::<Layer>().ok(),
+            entry.get_component::<Opaque>().ok(),
+            entry.get_component::<Barrier>().ok(),
+        )
+    }
+
+    // Opaque objects block FOV, and barriers block movement
+    pub fn from_components(
+        entity: Entity,
+        layer: Option<&Layer>,
+        opaque: Option<&Opaque>,
+        barrier: Option<&Barrier>,
+    ) -> Self {
+        Self::new(
+            entity,
+            layer.cloned().unwrap_or_default(),
+            opaque.is_some(),
+            barrier.is_some(),
+        )
+    }
+}
+
+// Objects at a position, kept sorted by layer from the bottom up
+type ObjectStack = Vec<Object>;
+pub type Map = MapBase<ObjectStack>;
+
+// A map stored as chunks, which are only allocated once something is placed in them
+#[derive(Debug)]
+pub struct MapBase<C> {
+    chunks: HashMap<Vector<i32>, Chunk<C>>, // Keyed by chunk coordinates
+    size: Vector<i32>,
+    locations: HashMap<Entity, Vector<i32>>, // The position of the stack each entity is in
+}
+
+impl<C: AsRef<ObjectStack>> MapBase<C> {
+    pub fn new(size: Vector<i32>) -> Self {
+        Self {
+            chunks: HashMap::new(),
+            size,
+            locations: HashMap::new(),
+        }
+    }
+
+    // Build a map from every one of its stacks, in row order
+    fn from_stacks(stacks: Vec<C>, size: Vector<i32>) -> Self {
+        let mut map = Self::new(size);
+
+        for (index, obj_stack) in stacks.into_iter().enumerate() {
+            let position = map.index_to_vector(index);
+            let chunk = chunk_of(position);
+
+            for obj in obj_stack.as_ref() {
+                map.locations.insert(obj.entity, position);
+            }
+
+            map.chunks
+                .entry(chunk)
+                .or_insert_with(|| Chunk::new(chunk, size))
+                .stacks
+                .push(obj_stack);
+        }
+
+        map
+    }
+
+    pub fn in_bounds(&self, position: Vector<i32>) -> bool {
+        position.0 >= 0 && position.1 >= 0 && position.0 < self.size.0 && position.1 < self.size.1
+    }
+
+    fn index_to_vector(&self, index: usize) -> Vector<i32> {
+        let width = self.size.0.max(1) as usize;
+
+        Vector((index % width) as i32, (index / width) as i32)
+    }
+
+    // The stack at a position, if the position is on the map and its chunk is allocated
+    pub fn get_objects(&self, position: Vector<i32>) -> Option<&C> {
+        if !self.in_bounds(position) {
+            return None;
+        }
+
+        self.chunks.get(&chunk_of(position))?.get(position)
+    }
+
+    // Each row of allocated stacks, from the top of the map down
+    pub fn rows(&self) -> impl Iterator<Item = impl Iterator<Item = (Vector<i32>, &C)>> {
+        (0..self.size.1).map(move |y| self.rect(Vector(0, y), Vector(self.size.0, 1)))
+    }
+
+    // The chunks that are allocated, in chunk coordinates
+    pub fn chunks(&self) -> impl Iterator<Item = Vector<i32>> + '_ {
+        self.chunks.keys().cloned()
+    }
+
+    // The stacks at the given positions, skipping any that are off the map or unallocated
+    pub fn stacks_at<I: IntoIterator<Item = Vector<i32>>>(
+        &self,
+        positions: I,
+    ) -> impl Iterator<Item = (Vector<i32>, &C)> {
+        positions
+            .into_iter()
+            .filter_map(move |position| Some((position, self.get_objects(position)?)))
+    }
+
+    pub fn rect(
+        &self,
+        position: Vector<i32>,
+        size: Vector<i32>,
+    ) -> impl Iterator<Item = (Vector<i32>, &C)> {
+        self.stacks_at(geometry::rect(position, size))
+    }
+
+    pub fn circle(
+        &self,
+        centre: Vector<i32>,
+        radius: i32,
+    ) -> impl Iterator<Item = (Vector<i32>, &C)> {
+        self.stacks_at(geometry::circle(centre, radius))
+    }
+
+    pub fn line(
+        &self,
+        from: Vector<i32>,
+        to: Vector<i32>,
+    ) -> impl Iterator<Item = (Vector<i32>, &C)> {
+        self.stacks_at(geometry::line(from, to))
+    }
+
+    pub fn get_objects_at_entity(&self, entity: Entity) -> Option<&C> {
+        self.get_objects(self.position_of(entity)?)
+    }
+
+    pub fn position_of(&self, entity: Entity) -> Option<Vector<i32>> {
+        self.locations.get(&entity).cloned()
+    }
+
+    // The topmost object at a position, which is the one that is drawn
+    pub fn top(&self, position: Vector<i32>) -> Option<&Object> {
+        self.get_objects(position)?.as_ref().last()
+    }
+
+    // The topmost object at a position within a layer
+    pub fn top_in(&self, position: Vector<i32>, layer: Layer) -> Option<&Object> {
+        self.get_objects(position)?
+            .as_ref()
+            .iter()
+            .rev()
+            .find(|obj| obj.layer == layer)
+    }
+
+    // The topmost object at a position lying beneath a layer
+    pub fn top_beneath(&self, position: Vector<i32>, layer: Layer) -> Option<&Object> {
+        self.get_objects(position)?
+            .as_ref()
+            .iter()
+            .rev()
+            .find(|obj| obj.layer < layer)
+    }
+
+    // Whether anything at a position blocks movement (positions off the map or unallocated always do)
+    pub fn blocks_movement(&self, position: Vector<i32>) -> bool {
+        self.get_objects(position)
+            .is_none_or(|objects| objects.as_ref().iter().any(|obj| obj.block_movement))
+    }
+
+    // Check the entity index against the stacks, and against the given entity positions
+    pub fn validate<I: IntoIterator<Item = (Entity, Vector<i32>)>>(
+        &self,
+        positions: I,
+    ) -> Result<(), String> {
+        for (entity, position) in &self.locations {
+            if !self
+                .get_objects(*position)
+                .is_some_and(|obj_stack| obj_stack.as_ref().iter().any(|obj| obj.entity == *entity))
+            {
+                return Err(format!(
+                    "{:?} is indexed at {:?} but is not in that stack",
+                    entity, position
+                ));
+            }
+        }
+
+        for (position, obj_stack) in self.chunks.values().flat_map(|chunk| chunk.iter()) {
+            let obj_stack = obj_stack.as_ref();
+
+            if obj_stack
+                .windows(2)
+                .any(|pair| pair[0].layer > pair[1].layer)
+            {
+                return Err(format!("The stack at {:?} is out of layer order", position));
+            }
+
+            for obj in obj_stack {
+                if self.position_of(obj.entity) != Some(position) {
+                    return Err(format!(
+                        "{:?} is in the stack at {:?} but is indexed at {:?}",
+                        obj.entity,
+                        position,
+                        self.position_of(obj.entity)
+                    ));
+                }
+            }
+        }
+
+        for (entity, position) in positions {
+            if self.position_of(entity) != Some(position) {
+                return Err(format!(
+                    "{:?} has position {:?} but is indexed at {:?}",
+                    entity,
+                    position,
+                    self.position_of(entity)
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    // A view of a rectangle of the map, addressed from its top-left corner,
+    // or nothing if the rectangle does not lie wholly on the map and in allocated chunks
+    pub fn region(&self, position: Vector<i32>, size: Vector<i32>) -> Option<MapBase<&C>> {
+        if !self.contains_rect(position, size) {
+            return None;
+        }
+
+        let region = geometry::rect(position, size)
+            .map(|point| self.get_objects(point))
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(MapBase::from_stacks(region, size))
+    }
+
+    fn contains_rect(&self, position: Vector<i32>, size: Vector<i32>) -> bool {
+        size.0 >= 0
+            && size.1 >= 0
+            && position.0 >= 0
+            && position.1 >= 0
+            && position.0 + size.0 <= self.size.0
+            && position.1 + size.1 <= self.size.1
+    }
+
+    pub fn neighbours(&self, position: Vector<i32>) -> Vec<&C> {
+        let mut neighbouring_obj_stacks = Vec::new();
+
+        for direction in Direction::all().iter() {
+            if let Some(obj_stack) = self.get_objects(position + direction.as_unit_vector()) {
+                neighbouring_obj_stacks.push(obj_stack);
+            }
+        }
+
+        neighbouring_obj_stacks
+    }
+}
+
+impl<C: AsRef<ObjectStack> + AsMut<ObjectStack>> MapBase<C> {
+    // A mutable view of a rectangle of the map, as for region. Only the chunks the rectangle
+    // overlaps are walked
+    pub fn region_mut(
+        &mut self,
+        position: Vector<i32>,
+        size: Vector<i32>,
+    ) -> Option<MapBase<&mut C>> {
+        if !self.contains_rect(position, size) {
+            return None;
+        }
+
+        let (first, last) = (chunk_of(position), chunk_of(position + size - Vector(1, 1)));
+        let within = |point: &Vector<i32>| {
+            point.0 >= position.0
+                && point.1 >= position.1
+                && point.0 < position.0 + size.0
+                && point.1 < position.1 + size.1
+        };
+        let mut region = self
+            .chunks
+            .iter_mut()
+            .filter(|(chunk, _)| {
+                chunk.0 >= first.0 && chunk.1 >= first.1 && chunk.0 <= last.0 && chunk.1 <= last.1
+            })
+            .flat_map(|(_, chunk)| chunk.iter_mut())
+            .filter(|(point, _)| within(point))
+            .collect::<Vec<_>>();
+
+        if region.len() != (size.0 * size.1) as usize {
+            return None;
+        }
+
+        region.sort_by_key(|(point, _)| (point.1, point.0));
+
+        Some(MapBase::from_stacks(
+            region.into_iter().map(|(_, obj_stack)| obj_stack).collect(),
+            size,
+        ))
+    }
+
+    fn stack_mut(&mut self, position: Vector<i32>) -> Option<&mut C> {
+        self.chunks.get_mut(&chunk_of(position))?.get_mut(position)
+    }
+
+    // The height of an entity within its stack
+    pub fn height_of(&self, entity: Entity) -> Option<usize> {
+        self.get_objects_at_entity(entity)?
+            .as_ref()
+            .iter()
+            .position(|obj| obj.entity == entity)
+    }
+
+    // Take an object off the map
+    pub fn remove(&mut self, entity: Entity) -> Option<Object> {
+        let height = self.height_of(entity)?;
+        let position = self.locations.remove(&entity)?;
+
+        Some(self.stack_mut(position)?.as_mut().remove(height))
+    }
+
+    // Drop a chunk and everything in it from the map, returning the entities it held
+    pub fn unload(&mut self, chunk: Vector<i32>) -> Vec<Entity> {
+        let entities = self.chunks.remove(&chunk).map_or(Vec::new(), |chunk| {
+            chunk
+                .stacks
+                .iter()
+                .flat_map(|obj_stack| obj_stack.as_ref().iter().map(|obj| obj.entity))
+                .collect()
+        });
+
+        for entity in &entities {
+            self.locations.remove(entity);
+        }
+
+        entities
+    }
+}
+
+impl<C: AsRef<ObjectStack> + AsMut<ObjectStack> + Default> MapBase<C> {
+    // Place an object on top of its layer at a position, taking it from wherever it was before.
+    // The chunk the position lies in is allocated if it is not already. Positions off the map are
+    // ignored, returning whether the object was placed
+    pub fn place(&mut self, position: Vector<i32>, object: Object) -> bool {
+        if !self.in_bounds(position) {
+            return false;
+        }
+
+        self.remove(object.entity);
+        self.locations.insert(object.entity, position);
+
+        let (chunk, size) = (chunk_of(position), self.size);
+        let obj_stack = self
+            .chunks
+            .entry(chunk)
+            .or_insert_with(|| Chunk::filled(chunk, size))
+            .get_mut(position)
+            .unwrap()
+            .as_mut();
+        let height = obj_stack
+            .iter()
+            .position(|obj| obj.layer > object.layer)
+            .unwrap_or(obj_stack.len());
+
+        obj_stack.insert(height, object);
+        true
+    }
+
+    // Move an entity and its position to a new stack, leaving it where it is if the new position
+    // is off the map
+    pub fn move_entity(
+        &mut self,
+        entity: Entity,
+        position: &mut Position,
+        new_position: Vector<i32>,
+    ) {
+        if !self.in_bounds(new_position) {
+            return;
+        }
+
+        if let Some(object) = self.remove(entity) {
+            self.place(new_position, object);
+        }
+
+        position.vector = new_position; // Mutate the component
+    }
+
+    // Bring an entity's object in line with its position and components
+    pub fn sync(&mut self, position: Vector<i32>, object: Object) {
+        if let (Some(current), Some(height)) = (
+            self.position_of(object.entity),
+            self.height_of(object.entity),
+        ) {
+            let obj = &mut self.stack_mut(current).unwrap().as_mut()[height];
+
+            // Objects that stay within their stack and layer are updated in place
+            if current == position && obj.layer == object.layer {
+                *obj = object;
+                return;
+            }
+        }
+
+        self.place(position, object);
+    }
+}
+
+// Indexing panics for positions off the map or in unallocated chunks, as slices do when out of
+// range; get_objects is the fallible form. Stacks changed through IndexMut are not reindexed, so
+// entities should be moved with place and remove
+impl<T: Into<Vector<i32>>, C: AsRef<ObjectStack>> Index<T> for MapBase<C> {
+    type Output = C;
+
+    fn index(&self, index: T) -> &Self::Output {
+        let position = index.into();
+
+        match self.get_objects(position) {
+            Some(obj_stack) => obj_stack,
+            None => panic!("No stack at {:?} on the map", position),
+        }
+    }
+}
+
+impl<T: Into<Vector<i32>>, C: AsRef<ObjectStack> + AsMut<ObjectStack>> IndexMut<T> for MapBase<C> {
+    fn index_mut(&mut self, index: T) -> &mut Self::Output {
+        let position = index.into();
+        let stack = match self.in_bounds(position) {
+            true => self.stack_mut(position),
+            false => None,
+        };
+
+        match stack {
+            Some(obj_stack) => obj_stack,
+            None => panic!("No stack at {:?} on the map", position),
+        }
+    }
+}
+
+impl<C: AsRef<ObjectStack>> BaseMap for MapBase<C> {
+    fn is_opaque(&self, index: usize) -> bool {
+        self.get_objects(self.index_to_vector(index))
+            .is_some_and(|items| items.as_ref().iter().any(|obj| obj.block_fov))
+    }
+}
+
+impl<C: AsRef<ObjectStack>> Algorithm2D for MapBase<C> {
+    fn point2d_to_index(&self, point: Point) -> usize {
+        (point.x + point.y * self.size.0) as usize
+    }
+
+    fn index_to_point2d(&self, index: usize) -> Point {
+        self.index_to_vector(index).into()
+    }
+
+    fn dimensions(&self) -> Point {
+        self.size.into()
+    }
+
+    fn in_bounds(&self, point: Point) -> bool {
+        MapBase::in_bounds(self, point.into())
+    }
+}
+
+pub struct Generator<'a> {
+    world: &'a mut World,
+    map: Arc<RwLock<Map>>,
+}
+
+impl<'a> Generator<'a> {
+    fn new(world: &'a mut World, map: Arc<RwLock<Map>>) -> Self {
+        Self { world, map }
+    }
+
+    pub fn room(
+        &mut self,
+        position: Vector<i32>,
+        dimensions: Vector<i32>,
+        floor: &Floor,
+        wall: &Wall,
+    ) {
+        let mut commands = CommandBuffer::new(self.world);
+
+        // Flooring
+        for y in (position.1 + 1)..dimensions.1 {
+            for x in (position.0 + 1)..dimensions.0 {
+                floor.spawn(&mut commands, self.map.clone(), (x, y));
+            }
+        }
+
+        // Top and bottom walls
+        for x in (position.0 + 1)..dimensions.0 {
+            wall.spawn(&mut commands, self.map.clone(), (x, position.1));
+            wall.spawn(&mut commands, self.map.clone(), (x, dimensions.1));
+        }
+
+        // Left and right walls
+        for y in position.1..dimensions.1 {
+            wall.spawn(&mut commands, self.map.clone(), (position.0, y));
+            wall.spawn(&mut commands, self.map.clone(), (dimensions.0, y));
+        }
+
+        commands.flush(self.world);
+    }
+
+    // Spawning places objects on the map, so the map is only read in short scopes that end
+    // before anything is spawned
+    pub fn generate_caves(&mut self, floor: &Floor, wall: &Wall, enhancement_passes: u32) {
+        let mut commands = CommandBuffer::new(self.world);
+        let mut rng = RandomNumberGenerator::new();
+        let map_dimensions = self.map.read().unwrap().dimensions();
+
+        // Randomly spawn walls
+        for position in geometry::rect(Vector(0, 0), map_dimensions.into()) {
+            if rng.rand::<f32>() < 0.35 {
+                wall.spawn(&mut commands, self.map.clone(), position);
+            } else {
+                floor.spawn(&mut commands, self.map.clone(), position);
+            }
+        }
+
+        commands.flush(self.world);
+
+        // Repeatedly enhance tha map, making smoother caves and removing random walls
+        for _i in 0..enhancement_passes {
+            let mut commands = CommandBuffer::new(self.world);
+
+            for (entity, position, barrier) in <(Entity, &Position, Option<&Barrier>)>::query()
+                .iter(self.world)
+                .map(|(e, p, w)| (*e, *p, w.cloned()))
+                .collect::<Vec<_>>()
+            {
+                let wall_neighbours = self
+                    .map
+                    .read()
+                    .unwrap()
+                    .neighbours(position.vector)
+                    .into_iter()
+                    .flat_map(|stack| stack.iter())
+                    .filter(|obj| {
+                        self.world
+                            .entry_ref(obj.entity)
+                            .unwrap()
+                            .get_component::<Barrier>()
+                            .is_ok()
+                    })
+                    .count();
+
+                let become_wall = barrier.is_none() && wall_neighbours > 4;
+                let delete_wall = barrier.is_some() && wall_neighbours < 2;
+
+                if become_wall || delete_wall {
+                    self.map.write().unwrap().remove(entity);
+                    self.world.remove(entity);
+
+                    if become_wall {
+                        wall.spawn(&mut commands, self.map.clone(), position.vector);
+                    } else {
+                        floor.spawn(&mut commands, self.map.clone(), position.vector);
+                    }
+                }
+
+                commands.flush(self.world);
+            }
+        }
+    }
+
+    pub fn generate(self) {}
+}
+
+pub struct Populator<'a> {
+    world: &'a mut World,
+    map: Arc<RwLock<Map>>,
+}
+
+impl<'a> Populator<'a> {
+    pub fn new(world: &'a mut World, map: Arc<RwLock<Map>>) -> Self {
+        Self { world, map }
+    }
+
+    pub fn spawn_player(&self) {
+        let mut rng = RandomNumberGenerator::new();
+
+        rng.random_slice_entry(
+            <(&Tile, &Position)>::query()
+                .iter(self.world)
+                .collect::<Vec<_>>()
+                .as_slice(),
+        )
+        .unwrap()
+        .1
+        .vector;
+    }
+
+    pub fn populate(self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    const CASES: usize = 200;
+
+    // A map of a random size with a single object in every stack
+    fn filled(rng: &mut StdRng, world: &mut World) -> Map {
+        let size = Vector(rng.gen_range(1, 80), rng.gen_range(1, 80));
+        let mut map = Map::new(size);
+
+        for position in geometry::rect(Vector(0, 0), size) {
+            let entity = world.push(());
+            map.place(position, Object::new(entity, Layer::Terrain, false, false));
+        }
+
+        map
+    }
+
+    // A point somewhere around the map, which may lie off it
+    fn around(rng: &mut StdRng, map: &Map) -> Vector<i32> {
+        let size = map.size;
+
+        Vector(
+            rng.gen_range(-10, size.0 + 10),
+            rng.gen_range(-10, size.1 + 10),
+        )
+    }
+
+    fn top(stack: Option<&ObjectStack>) -> Option<Entity> {
+        stack.and_then(|stack| stack.last()).map(|obj| obj.entity)
+    }
+
+    #[test]
+    fn index_and_point_round_trip() {
+        let mut rng = StdRng::seed_from_u64(35);
+
+        for _ in 0..CASES {
+            let map = Map::new(Vector(rng.gen_range(1, 80), rng.gen_range(1, 80)));
+            let size = map.size;
+
+            for index in 0..(size.0 * size.1) as usize {
+                let point = map.index_to_point2d(index);
+
+                assert!(Algorithm2D::in_bounds(&map, point));
+                assert_eq!(map.point2d_to_index(point), index);
+            }
+
+            let point = Point::new(rng.gen_range(0, size.0), rng.gen_range(0, size.1));
+            assert_eq!(map.index_to_point2d(map.point2d_to_index(point)), point);
+        }
+    }
+
+    #[test]
+    fn in_bounds_agrees_with_get_objects() {
+        let mut rng = StdRng::seed_from_u64(35);
+        let mut world = World::default();
+
+        for _ in 0..CASES / 10 {
+            let mut map = filled(&mut rng, &mut world);
+
+            for _ in 0..CASES {
+                let position = around(&mut rng, &map);
+
+                assert_eq!(map.in_bounds(position), map.get_objects(position).is_some());
+            }
+
+            // Placing off the map is refused rather than indexing the object anywhere
+            let entity = world.push(());
+            let off = Vector(-1, rng.gen_range(0, map.size.1));
+
+            assert!(!map.place(off, Object::new(entity, Layer::Item, false, false)));
+            assert_eq!(map.position_of(entity), None);
+        }
+    }
+
+    #[test]
+    fn region_views_the_map_from_its_corner() {
+        let mut rng = StdRng::seed_from_u64(35);
+        let mut world = World::default();
+
+        for _ in 0..CASES / 10 {
+            let map = filled(&mut rng, &mut world);
+
+            for _ in 0..CASES {
+                let position = around(&mut rng, &map);
+                let size = Vector(rng.gen_range(0, 12), rng.gen_range(0, 12));
+                let fits = position.0 >= 0
+                    && position.1 >= 0
+                    && position.0 + size.0 <= map.size.0
+                    && position.1 + size.1 <= map.size.1;
+                let region = map.region(position, size);
+
+                assert_eq!(region.is_some(), fits);
+
+                if let Some(region) = region {
+                    for offset in geometry::rect(Vector(0, 0), size) {
+                        assert_eq!(
+                            top(region.get_objects(offset).copied()),
+                            top(map.get_objects(position + offset))
+                        );
+                    }
+
+                    assert!(region.get_objects(size).is_none());
+                    assert!(region.get_objects(Vector(-1, 0)).is_none());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn region_needs_every_chunk_allocated() {
+        let mut world = World::default();
+        let mut map = Map::new(Vector(80, 80));
+        let entity = world.push(());
+
+        map.place(
+            Vector(0, 0),
+            Object::new(entity, Layer::Terrain, false, false),
+        );
+
+        assert!(map.region(Vector(0, 0), Vector(4, 4)).is_some());
+        assert!(map.region(Vector(30, 30), Vector(4, 4)).is_none());
+    }
+
+    #[test]
+    fn region_mut_views_the_same_stacks_as_region() {
+        let mut rng = StdRng::seed_from_u64(35);
+        let mut world = World::default();
+
+        for _ in 0..CASES / 10 {
+            let mut map = filled(&mut rng, &mut world);
+
+            for _ in 0..CASES {
+                let position = around(&mut rng, &map);
+                let size = Vector(rng.gen_range(0, 40), rng.gen_range(0, 40));
+                let expected = map.region(position, size).map(|region| {
+                    geometry::rect(Vector(0, 0), size)
+                        .map(|offset| top(region.get_objects(offset).copied()))
+                        .collect::<Vec<_>>()
+                });
+                let actual = map.region_mut(position, size).map(|region| {
+                    geometry::rect(Vector(0, 0), size)
+                        .map(|offset| top(region.get_objects(offset).map(|stack| &**stack)))
+                        .collect::<Vec<_>>()
+                });
+
+                assert_eq!(actual, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn shapes_skip_stacks_off_the_map() {
+        let mut rng = StdRng::seed_from_u64(35);
+        let mut world = World::default();
+
+        for _ in 0..CASES / 10 {
+            let map = filled(&mut rng, &mut world);
+            let (from, to) = (around(&mut rng, &map), around(&mut rng, &map));
+            let radius = rng.gen_range(0, 10);
+
+            assert!(map
+                .rect(from, to - from)
+                .chain(map.circle(from, radius))
+                .chain(map.line(from, to))
+                .all(|(position, stack)| map.in_bounds(position)
+                    && top(Some(stack)) == top(map.get_objects(position))));
+            assert_eq!(
+                map.line(from, to).count(),
+                geometry::line(from, to)
+                    .filter(|position| map.in_bounds(*position))
+                    .count()
+            );
+            assert_eq!(
+                map.rows().map(|row| row.count()).sum::<usize>(),
+                (map.size.0 * map.size.1) as usize
+            );
+        }
+    }
+
+    #[test]
+    fn indexing_matches_get_objects() {
+        let mut rng = StdRng::seed_from_u64(35);
+        let mut world = World::default();
+        let mut map = filled(&mut rng, &mut world);
+        let position = Vector(map.size.0 - 1, map.size.1 - 1);
+        let entity = top(map.get_objects(position));
+
+        assert_eq!(top(Some(&map[position])), entity);
+        assert_eq!(top(Some(&map[(0, 0)])), top(map.get_objects(Vector(0, 0))));
+        assert_eq!(map[position].pop().map(|obj| obj.entity), entity);
+    }
+
+    #[test]
+    #[should_panic]
+    fn indexing_off_the_map_panics() {
+        let map = Map::new(Vector(10, 10));
+
+        let _ = &map[(10, 0)];
+    }
+}
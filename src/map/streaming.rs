@@ -0,0 +1,258 @@
+use components::attributes::Attributes;
+use components::barrier::Barrier;
+use components::description::Description;
+use components::door::Door;
+use components::durability::Durability;
+use components::effects::Effects;
+use components::encumbrance::Encumbrance;
+use components::equipment::Equipment;
+use components::experience::Experience;
+use components::fire::Fire;
+use components::gas::Gas;
+use components::glyph::Glyph;
+use components::hearing::Hearing;
+use components::ignites::Ignites;
+use components::layer::Layer;
+use components::light::Light;
+use components::loot::Loot;
+use components::needs::Needs;
+use components::opaque::Opaque;
+use components::perishable::Perishable;
+use components::position::Position;
+use components::quantity::Quantity;
+use components::skills::Skills;
+use components::spawn::Spawn;
+use components::stained::Stained;
+use components::status_effects::StatusEffects;
+use components::storage::Storage;
+use components::surface::Surface;
+use components::tags::Tags;
+use components::template::Template;
+use components::tile::Tile;
+use components::tracks::Tracks;
+use components::trader::Trader;
+use components::trigger::Trigger;
+use components::unidentified::Unidentified;
+use components::value::Value;
+use components::weight::Weight;
+
+use legion::query::component;
+use legion::serialize::{Registry, UnknownType};
+use legion::*;
+use map::chunk::chunk_of;
+use map::Map;
+use serde::de::DeserializeSeed;
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use vector::Vector;
+
+// Chunks within this many chunks of the focus are kept in the world, and the rest are stored
+const LOADED_DISTANCE: i32 = 2;
+
+// Marks the entities that are leaving the world with their chunk
+struct Unloading;
+
+// Moves chunks, along with the entities in them, between the world and disk
+pub struct Streamer {
+    registry: Registry<String>,
+    directory: PathBuf,
+    stored: HashSet<Vector<i32>>,
+    kept: HashSet<Vector<i32>>, // Chunks that could not be stored, and stay in the world instead
+}
+
+impl Streamer {
+    pub fn new<P: AsRef<Path>>(directory: P) -> Self {
+        // Chunks stored by an earlier run are left alone, as only those stored by this one are
+        // ever brought back
+        let directory = directory.as_ref().to_path_buf();
+
+        // Components that are not registered (such as those only the player has) are left behind
+        let mut registry = Registry::<String>::default();
+        registry.on_unknown(UnknownType::Ignore);
+        registry.register::<Position>(String::from("position"));
+        registry.register::<Layer>(String::from("layer"));
+        registry.register::<Glyph>(String::from("glyph"));
+        registry.register::<Description>(String::from("description"));
+        registry.register::<Weight>(String::from("weight"));
+        registry.register::<Barrier>(String::from("barrier"));
+        registry.register::<Opaque>(String::from("opaque"));
+        registry.register::<Tile>(String::from("tile"));
+        registry.register::<Light>(String::from("light"));
+        registry.register::<Effects>(String::from("effects"));
+        registry.register::<Spawn>(String::from("spawn"));
+        registry.register::<Equipment>(String::from("equipment"));
+        registry.register::<Storage>(String::from("storage"));
+        registry.register::<Door>(String::from("door"));
+        registry.register::<Durability>(String::from("durability"));
+        registry.register::<Trigger>(String::from("trigger"));
+        registry.register::<Surface>(String::from("surface"));
+        registry.register::<Stained>(String::from("stained"));
+        registry.register::<Tracks>(String::from("tracks"));
+        registry.register::<Fire>(String::from("fire"));
+        registry.register::<Gas>(String::from("gas"));
+        registry.register::<Ignites>(String::from("ignites"));
+        registry.register::<Hearing>(String::from("hearing"));
+        registry.register::<Attributes>(String::from("attributes"));
+        registry.register::<Encumbrance>(String::from("encumbrance"));
+        registry.register::<Experience>(String::from("experience"));
+        registry.register::<Skills>(String::from("skills"));
+        registry.register::<StatusEffects>(String::from("status_effects"));
+        registry.register::<Needs>(String::from("needs"));
+        registry.register::<Perishable>(String::from("perishable"));
+        registry.register::<Template>(String::from("template"));
+        registry.register::<Tags>(String::from("tags"));
+        registry.register::<Unidentified>(String::from("unidentified"));
+        registry.register::<Quantity>(String::from("quantity"));
+        registry.register::<Loot>(String::from("loot"));
+        registry.register::<Value>(String::from("value"));
+        registry.register::<Trader>(String::from("trader"));
+
+        Self {
+            registry,
+            directory,
+            stored: HashSet::new(),
+            kept: HashSet::new(),
+        }
+    }
+
+    fn path_of(&self, chunk: Vector<i32>) -> PathBuf {
+        self.directory.join(format!("{}_{}.json", chunk.0, chunk.1))
+    }
+
+    // Store the chunks that are far from a focus, and bring back those that are near it
+    pub fn stream(
+        &mut self,
+        world: &mut World,
+        map: &Arc<RwLock<Map>>,
+        focus: Vector<i32>,
+    ) -> io::Result<()> {
+        let focus = chunk_of(focus);
+        let distance =
+            |chunk: Vector<i32>| (chunk.0 - focus.0).abs().max((chunk.1 - focus.1).abs());
+
+        let distant = map
+            .read()
+            .unwrap()
+            .chunks()
+            .filter(|chunk| distance(*chunk) > LOADED_DISTANCE && !self.kept.contains(chunk))
+            .collect::<Vec<_>>();
+
+        for chunk in distant {
+            self.unload(world, map, chunk)?;
+        }
+
+        let near = self
+            .stored
+            .iter()
+            .cloned()
+            .filter(|chunk| distance(*chunk) <= LOADED_DISTANCE)
+            .collect::<Vec<_>>();
+
+        for chunk in near {
+            self.load(world, chunk)?;
+        }
+
+        Ok(())
+    }
+
+    fn unload(
+        &mut self,
+        world: &mut World,
+        map: &Arc<RwLock<Map>>,
+        chunk: Vector<i32>,
+    ) -> io::Result<()> {
+        let mut entities = map.write().unwrap().unload(chunk);
+
+        // Whatever is carried goes along with whoever carries it
+        let mut index = 0;
+        while index < entities.len() {
+            if let Ok(entry) = world.entry_ref(entities[index]) {
+                let mut carried = Vec::new();
+
+                if let Ok(equipment) = entry.get_component::<Equipment>() {
+                    carried.extend(equipment.held);
+                    carried.extend(equipment.storage);
+                    carried.extend(equipment.wearables.iter().cloned());
+                }
+
+                if let Ok(storage) = entry.get_component::<Storage>() {
+                    carried.extend(storage.objects.iter().cloned());
+                }
+
+                entities.extend(carried);
+            }
+
+            index += 1;
+        }
+
+        for entity in &entities {
+            if let Some(mut entry) = world.entry(*entity) {
+                entry.add_component(Unloading);
+            }
+        }
+
+        let mut unloaded = World::default();
+        unloaded.move_from(world, &component::<Unloading>());
+
+        let stored = fs::create_dir_all(&self.directory)
+            .and_then(|_| File::create(self.path_of(chunk)))
+            .and_then(|file| {
+                Ok(serde_json::to_writer(
+                    BufWriter::new(file),
+                    &unloaded.as_serializable(any(), &self.registry),
+                )?)
+            });
+
+        // Whatever could not be stored is put back, to be placed on the map again as it is synced
+        if let Err(error) = stored {
+            world.move_from(&mut unloaded, &any());
+
+            for entity in &entities {
+                if let Some(mut entry) = world.entry(*entity) {
+                    entry.remove_component::<Unloading>();
+                }
+            }
+
+            self.kept.insert(chunk);
+
+            return Err(failed("store", chunk, error));
+        }
+
+        self.stored.insert(chunk);
+
+        Ok(())
+    }
+
+    // Entities keep their identities when they are brought back, and are placed back
+    // on the map as they are synced
+    fn load(&mut self, world: &mut World, chunk: Vector<i32>) -> io::Result<()> {
+        // A chunk that cannot be brought back is not tried again
+        self.stored.remove(&chunk);
+
+        let path = self.path_of(chunk);
+        let file = File::open(&path).map_err(|error| failed("load", chunk, error))?;
+
+        self.registry
+            .as_deserialize_into_world(world)
+            .deserialize(&mut serde_json::Deserializer::from_reader(BufReader::new(
+                file,
+            )))
+            .map_err(|error| failed("load", chunk, error.into()))?;
+
+        fs::remove_file(path).map_err(|error| failed("remove", chunk, error))
+    }
+}
+
+// Say which chunk an error came from
+fn failed(action: &str, chunk: Vector<i32>, error: io::Error) -> io::Error {
+    io::Error::new(
+        error.kind(),
+        format!(
+            "Failed to {} chunk {}, {}: {}",
+            action, chunk.0, chunk.1, error
+        ),
+    )
+}
@@ -0,0 +1,63 @@
+use components::tags::Tags;
+use components::template::Template;
+use legion::Entity;
+use serde::Deserialize;
+
+// Something a recipe calls for, either a particular item or any item with a tag
+#[derive(Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum Ingredient {
+    Template(String),
+    Tag(String),
+}
+
+impl Ingredient {
+    pub fn accepts(&self, template: &Template, tags: &Tags) -> bool {
+        match self {
+            Ingredient::Template(name) => template.name == *name,
+            Ingredient::Tag(tag) => tags.has(tag),
+        }
+    }
+}
+
+// A way of combining items into others. Inputs are used up in the making, tools are not
+#[derive(Deserialize, Debug, Clone)]
+pub struct Recipe {
+    pub inputs: Vec<Ingredient>,
+    #[serde(default)]
+    pub tools: Vec<Ingredient>,
+    pub outputs: Vec<String>,
+}
+
+impl Recipe {
+    // The items that would be used up making this from those at hand, if everything needed is
+    // there. Each item can only fill one part of the recipe
+    pub fn inputs_from(&self, items: &[(Entity, Template, Tags)]) -> Option<Vec<Entity>> {
+        let mut unused = items.iter().collect::<Vec<_>>();
+        let mut inputs = Vec::new();
+
+        // Tools are set aside first, and particular items before any that will do
+        let mut wanted = self
+            .tools
+            .iter()
+            .map(|tool| (tool, false))
+            .chain(self.inputs.iter().map(|input| (input, true)))
+            .collect::<Vec<_>>();
+        wanted.sort_by_key(|(ingredient, used_up)| {
+            (*used_up, matches!(ingredient, Ingredient::Tag(_)))
+        });
+
+        for (ingredient, used_up) in wanted {
+            let index = unused
+                .iter()
+                .position(|(_, template, tags)| ingredient.accepts(template, tags))?;
+            let (entity, _, _) = unused.remove(index);
+
+            if used_up {
+                inputs.push(*entity);
+            }
+        }
+
+        Some(inputs)
+    }
+}
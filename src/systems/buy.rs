@@ -0,0 +1,149 @@
+use clock::Clock;
+use components::actions::buy::Buy;
+use components::attributes::Attributes;
+use components::description::Description;
+use components::equipment::Equipment;
+use components::glyph::Glyph;
+use components::perishable::Perishable;
+use components::quantity::{self, named, Quantity};
+use components::stained::Stained;
+use components::storage::Storage;
+use components::tags::Tags;
+use components::template::Template;
+use components::unidentified::Unidentified;
+use components::value::{asking_price, purse, Value};
+use components::weight::Weight;
+use interface::log::{Category, Log};
+use interface::Label;
+use label;
+use legion::systems::CommandBuffer;
+use legion::world::SubWorld;
+use legion::*;
+use std::sync::{Arc, RwLock};
+use systems::encumbrance::total_weight;
+use systems::stow::store;
+
+// Buy something a trader has in stock, paying from whatever coins are held or stored
+#[system]
+#[read_component(Buy)]
+#[write_component(Equipment)]
+#[read_component(Attributes)]
+#[read_component(Description)]
+#[read_component(Glyph)]
+#[read_component(Perishable)]
+#[read_component(Stained)]
+#[read_component(Tags)]
+#[read_component(Template)]
+#[read_component(Unidentified)]
+#[read_component(Value)]
+#[read_component(Weight)]
+#[write_component(Storage)]
+#[write_component(Quantity)]
+pub fn buy(
+    commands: &mut CommandBuffer,
+    world: &mut SubWorld,
+    #[resource] log: &Arc<RwLock<Log>>,
+    #[resource] clock: &Clock,
+) {
+    let mut log = log.write().unwrap();
+    let mut query = <(Entity, &Buy, &mut Equipment, Option<&Attributes>)>::query();
+    let (mut actors, mut rest) = world.split_for_query(&query);
+    let subworld = &mut rest;
+
+    for (entity, buy, equipment, attributes) in query.iter_mut(&mut actors) {
+        commands.remove_component::<Buy>(*entity);
+
+        let in_stock = <&Storage>::query()
+            .get(subworld, buy.trader)
+            .is_ok_and(|storage| storage.objects.contains(&buy.entity));
+
+        let price = match asking_price(subworld, buy.entity) {
+            Some(price) if in_stock => price,
+            _ => continue,
+        };
+
+        let purse = purse(subworld, equipment);
+        let coins: u32 = purse
+            .iter()
+            .map(|stack| quantity::count(subworld, *stack))
+            .sum();
+
+        if coins < price {
+            log.add(
+                clock.turn,
+                Category::Item,
+                label!["You cannot afford the ", named(subworld, buy.entity)],
+            );
+            continue;
+        }
+
+        // What is bought goes into empty hands, or else into storage
+        let into_hands = equipment.held.is_none();
+
+        if into_hands {
+            let grab_limit = attributes.cloned().unwrap_or_default().grab_limit();
+
+            if total_weight(subworld, buy.entity) > grab_limit {
+                log.add(
+                    clock.turn,
+                    Category::Item,
+                    label!["The ", named(subworld, buy.entity), " is too heavy to lift"],
+                );
+                continue;
+            }
+        } else if equipment.storage.is_none() {
+            log.add(clock.turn, Category::Item, label!["Your hands are full"]);
+            continue;
+        }
+
+        let label = label![
+            "You buy the ",
+            named(subworld, buy.entity),
+            " from the ",
+            named(subworld, buy.trader).0,
+            format!(" for {} coins", price)
+        ];
+
+        // Pay from each stack in turn, doing away with any that are spent
+        let mut owed = price;
+
+        for stack in purse {
+            let count = quantity::count(subworld, stack);
+            let paid = count.min(owed);
+            owed -= paid;
+
+            if paid < count {
+                if let Ok(quantity) = <&mut Quantity>::query().get_mut(subworld, stack) {
+                    quantity.count -= paid;
+                }
+            } else {
+                if equipment.held == Some(stack) {
+                    equipment.held = None;
+                }
+
+                if let Some(storage) = equipment.storage {
+                    if let Ok(storage) = <&mut Storage>::query().get_mut(subworld, storage) {
+                        storage.objects.retain(|object| *object != stack);
+                    }
+                }
+
+                commands.remove(stack);
+            }
+
+            if owed == 0 {
+                break;
+            }
+        }
+
+        if let Ok(storage) = <&mut Storage>::query().get_mut(subworld, buy.trader) {
+            storage.objects.retain(|object| *object != buy.entity);
+        }
+
+        match equipment.storage {
+            Some(storage) if !into_hands => store(commands, subworld, storage, buy.entity),
+            _ => equipment.held = Some(buy.entity),
+        }
+
+        log.add(clock.turn, Category::Item, label);
+    }
+}
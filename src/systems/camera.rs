@@ -0,0 +1,24 @@
+use camera::Camera;
+use components::meta::camera_focus::CameraFocus;
+use components::position::Position;
+use components::velocity::Velocity;
+use legion::systems::CommandBuffer;
+use legion::*;
+
+// Follow the focused entity, handing focus back once a lent focus has come to rest
+#[system(for_each)]
+pub fn camera(
+    commands: &mut CommandBuffer,
+    #[resource] camera: &mut Camera,
+    entity: &Entity,
+    position: &Position,
+    focus: &CameraFocus,
+    velocity: Option<&Velocity>,
+) {
+    camera.follow(position.vector);
+
+    if let (Some(owner), None) = (focus.returns_to, velocity) {
+        commands.remove_component::<CameraFocus>(*entity);
+        commands.add_component(owner, CameraFocus::default());
+    }
+}
@@ -0,0 +1,41 @@
+use clock::Clock;
+use components::position::Position;
+use interface::log::{Category, Log};
+use interface::Label;
+use label;
+use legion::world::SubWorld;
+use legion::*;
+use map::Map;
+use std::sync::{Arc, RwLock};
+
+// Check that the map's entity index agrees with its stacks and with every Position. Debug builds
+// stop at the first disagreement, while release builds report each new one in the log
+#[system]
+#[read_component(Position)]
+pub fn map_consistency(
+    #[state] reported: &mut Option<String>,
+    subworld: &SubWorld,
+    #[resource] map: &Arc<RwLock<Map>>,
+    #[resource] log: &Arc<RwLock<Log>>,
+    #[resource] clock: &Clock,
+) {
+    let mut query = <(Entity, &Position)>::query();
+    let positions = query
+        .iter(subworld)
+        .map(|(entity, position)| (*entity, position.vector));
+
+    match map.read().unwrap().validate(positions) {
+        Ok(_) => *reported = None,
+        Err(error) if cfg!(debug_assertions) => panic!("Map is inconsistent: {}", error),
+        Err(error) => {
+            if reported.as_ref() != Some(&error) {
+                log.write().unwrap().add(
+                    clock.turn,
+                    Category::System,
+                    label![format!("Map is inconsistent: {}", error)],
+                );
+                *reported = Some(error);
+            }
+        }
+    }
+}
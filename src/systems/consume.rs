@@ -0,0 +1,211 @@
+use clock::Clock;
+use components::actions::consume::Consume;
+use components::description::Description;
+use components::effects::{Effect, Effects};
+use components::equipment::Equipment;
+use components::glyph::Glyph;
+use components::identified::Identified;
+use components::needs::Needs;
+use components::perishable::Perishable;
+use components::quantity::Quantity;
+use components::status_effects::{Status, StatusEffect, StatusEffects};
+use components::surface::Surface;
+use components::tags::{Tags, EDIBLE, LUMINOUS};
+use components::template::Template;
+use components::unidentified::Unidentified;
+use interface::log::{Category, Log};
+use interface::Label;
+use label;
+use legion::systems::CommandBuffer;
+use legion::world::SubWorld;
+use legion::*;
+use map::Map;
+use std::sync::{Arc, RwLock};
+use systems::status::afflict;
+
+// How long rotten food makes its eater sick for, and glowing food makes its eater glow
+const ROTTEN_POISON_TURNS: u32 = 10;
+const LUMINOUS_GLOW_TURNS: u32 = 30;
+
+// Eat or drink something, or take a drink from a pool
+#[system]
+#[read_component(Consume)]
+#[write_component(Needs)]
+#[write_component(Equipment)]
+#[write_component(Identified)]
+#[read_component(Effects)]
+#[read_component(Description)]
+#[read_component(Glyph)]
+#[read_component(Perishable)]
+#[read_component(Tags)]
+#[read_component(Template)]
+#[read_component(Unidentified)]
+#[write_component(Surface)]
+#[write_component(Quantity)]
+#[write_component(StatusEffects)]
+pub fn consume(
+    commands: &mut CommandBuffer,
+    world: &mut SubWorld,
+    #[resource] map: &Arc<RwLock<Map>>,
+    #[resource] log: &Arc<RwLock<Log>>,
+    #[resource] clock: &Clock,
+) {
+    let mut log = log.write().unwrap();
+    let mut query = <(
+        Entity,
+        &Consume,
+        Option<&mut Needs>,
+        Option<&mut Equipment>,
+        Option<&mut Identified>,
+    )>::query();
+    let (mut actors, mut rest) = world.split_for_query(&query);
+    let subworld = &mut rest;
+
+    for (entity, consume, mut needs, equipment, identified) in query.iter_mut(&mut actors) {
+        commands.remove_component::<Consume>(*entity);
+
+        let (effects, name, colour, spoiled, pool, tags) = match subworld.entry_ref(consume.entity)
+        {
+            Ok(entry) => (
+                entry
+                    .get_component::<Effects>()
+                    .map_or(Vec::new(), |effects| effects.consume.clone()),
+                entry
+                    .get_component::<Description>()
+                    .cloned()
+                    .unwrap_or_default()
+                    .name
+                    .to_lowercase(),
+                entry
+                    .get_component::<Glyph>()
+                    .cloned()
+                    .unwrap_or_default()
+                    .colour,
+                entry
+                    .get_component::<Perishable>()
+                    .is_ok_and(|perishable| perishable.spoiled),
+                entry.get_component::<Surface>().is_ok(),
+                entry.get_component::<Tags>().cloned().unwrap_or_default(),
+            ),
+            Err(_) => continue,
+        };
+
+        // The template and real name of an item that has yet to be identified
+        let identity = subworld.entry_ref(consume.entity).ok().and_then(|entry| {
+            let template = entry.get_component::<Template>().ok()?;
+            let unidentified = entry.get_component::<Unidentified>().ok()?;
+
+            Some((
+                template.name.clone(),
+                unidentified.description.name.to_lowercase(),
+            ))
+        });
+
+        // Pools can be drunk from, but only edible items eaten
+        if effects.is_empty() || !(pool || tags.has(EDIBLE)) {
+            log.add(
+                clock.turn,
+                Category::Item,
+                label!["You cannot eat the ", (name, colour)],
+            );
+
+            continue;
+        }
+
+        let drink = effects
+            .iter()
+            .any(|effect| matches!(effect, Effect::Quench { .. }));
+        let verb = match (pool, drink) {
+            (true, _) => "drink from the ",
+            (false, true) => "drink the ",
+            (false, false) => "eat the ",
+        };
+
+        log.add(
+            clock.turn,
+            Category::Item,
+            label!["You ", verb, (name, colour)],
+        );
+
+        // Whatever it was, its effects give it away
+        if let (Some(identified), Some((template, real_name))) = (identified, identity) {
+            if identified.learn(&template) {
+                log.add(clock.turn, Category::Item, label!["It was a ", real_name]);
+            }
+        }
+
+        // Rotten food does only half the good, and makes its eater sick
+        let portion = |amount: u32| if spoiled { amount / 2 } else { amount };
+        let mut afflictions = Vec::new();
+
+        if spoiled {
+            afflictions.push(StatusEffect {
+                status: Status::Poisoned,
+                turns: ROTTEN_POISON_TURNS,
+                magnitude: 1,
+            });
+        }
+
+        if tags.has(LUMINOUS) {
+            afflictions.push(StatusEffect {
+                status: Status::Glowing,
+                turns: LUMINOUS_GLOW_TURNS,
+                magnitude: 1,
+            });
+        }
+
+        for effect in effects {
+            match effect {
+                Effect::Nourish { amount } => {
+                    if let Some(needs) = needs.as_deref_mut() {
+                        needs.nourish(portion(amount));
+                    }
+                }
+
+                Effect::Quench { amount } => {
+                    if let Some(needs) = needs.as_deref_mut() {
+                        needs.quench(portion(amount));
+                    }
+                }
+
+                Effect::Afflict { status, turns } => afflictions.push(StatusEffect {
+                    status,
+                    turns,
+                    magnitude: 1,
+                }),
+
+                // Stains only come of touching
+                Effect::Stain { .. } => {}
+            }
+        }
+
+        afflict(commands, subworld, *entity, afflictions);
+
+        // A pool is drunk a mouthful at a time, and a stack one at a time, but anything else is
+        // finished off
+        let finished = match <(Option<&mut Surface>, Option<&mut Quantity>)>::query()
+            .get_mut(subworld, consume.entity)
+        {
+            Ok((Some(surface), _)) => {
+                surface.volume = surface.volume.saturating_sub(1);
+                surface.volume == 0
+            }
+            Ok((None, Some(quantity))) => {
+                quantity.count = quantity.count.saturating_sub(1);
+                quantity.count == 0
+            }
+            _ => true,
+        };
+
+        if finished {
+            if let Some(equipment) = equipment {
+                if equipment.held == Some(consume.entity) {
+                    equipment.held = None;
+                }
+            }
+
+            map.write().unwrap().remove(consume.entity);
+            commands.remove(consume.entity);
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use clock::Clock;
+use components::actions::craft::Craft;
+use components::equipment::Equipment;
+use components::position::Position;
+use components::quantity::{self, Quantity};
+use components::storage::Storage;
+use components::tags::Tags;
+use components::template::Template;
+use interface::log::{Category, Log};
+use interface::Label;
+use label;
+use legion::systems::CommandBuffer;
+use legion::world::SubWorld;
+use legion::*;
+use map::Map;
+use std::sync::{Arc, RwLock};
+use {ITEMS, RECIPES};
+
+// Everything held or stored, with what recipes need to know about each. A stack appears once
+// for every item in it
+pub fn at_hand<W: EntityStore>(world: &W, equipment: &Equipment) -> Vec<(Entity, Template, Tags)> {
+    let mut items = equipment.held.into_iter().collect::<Vec<_>>();
+
+    if let Some(stored) = equipment
+        .storage
+        .and_then(|storage| world.entry_ref(storage).ok())
+        .and_then(|entry| {
+            entry
+                .get_component::<Storage>()
+                .ok()
+                .map(|storage| storage.objects.clone())
+        })
+    {
+        items.extend(
+            stored
+                .into_iter()
+                .filter(|object| Some(*object) != equipment.held),
+        );
+    }
+
+    items
+        .into_iter()
+        .filter_map(|item| {
+            let entry = world.entry_ref(item).ok()?;
+
+            Some((
+                item,
+                entry.get_component::<Template>().ok()?.clone(),
+                entry.get_component::<Tags>().cloned().unwrap_or_default(),
+            ))
+        })
+        .flat_map(|details| {
+            let count = quantity::count(world, details.0) as usize;
+            vec![details; count]
+        })
+        .collect()
+}
+
+// Make something from a recipe, using up its inputs and leaving the results underfoot
+#[system]
+#[read_component(Craft)]
+#[read_component(Position)]
+#[write_component(Equipment)]
+#[read_component(Template)]
+#[read_component(Tags)]
+#[write_component(Storage)]
+#[write_component(Quantity)]
+pub fn craft(
+    commands: &mut CommandBuffer,
+    world: &mut SubWorld,
+    #[resource] map: &Arc<RwLock<Map>>,
+    #[resource] log: &Arc<RwLock<Log>>,
+    #[resource] clock: &Clock,
+) {
+    let mut log = log.write().unwrap();
+    let mut query = <(Entity, &Craft, &Position, &mut Equipment)>::query();
+    let (mut actors, mut rest) = world.split_for_query(&query);
+    let subworld = &mut rest;
+
+    for (entity, craft, position, equipment) in query.iter_mut(&mut actors) {
+        commands.remove_component::<Craft>(*entity);
+
+        let recipe = match RECIPES.get(&craft.recipe) {
+            Some(recipe) => recipe,
+            None => continue,
+        };
+        let name = |template: &str| {
+            ITEMS
+                .get(template)
+                .map_or(String::from(template), |item| item.description.name.clone())
+                .to_lowercase()
+        };
+
+        let inputs = match recipe.inputs_from(&at_hand(subworld, equipment)) {
+            Some(inputs) => inputs,
+            None => {
+                let wanted = recipe
+                    .outputs
+                    .first()
+                    .map_or(String::new(), |output| name(output));
+
+                log.add(
+                    clock.turn,
+                    Category::Item,
+                    label!["You lack what you need to make the ", wanted],
+                );
+
+                continue;
+            }
+        };
+
+        for input in inputs {
+            // Stacks are used up one item at a time
+            if let Ok(quantity) = <&mut Quantity>::query().get_mut(subworld, input) {
+                if quantity.count > 1 {
+                    quantity.count -= 1;
+                    continue;
+                }
+            }
+
+            if equipment.held == Some(input) {
+                equipment.held = None;
+            }
+
+            if let Some(storage) = equipment
+                .storage
+                .and_then(|storage| <&mut Storage>::query().get_mut(subworld, storage).ok())
+            {
+                storage.objects.retain(|object| *object != input);
+            }
+
+            map.write().unwrap().remove(input);
+            commands.remove(input);
+        }
+
+        for output in &recipe.outputs {
+            if let Some(item) = ITEMS.get(output) {
+                item.spawn(commands, map.clone(), position.vector);
+
+                log.add(
+                    clock.turn,
+                    Category::Item,
+                    label!["You make the ", name(output)],
+                );
+            }
+        }
+    }
+}
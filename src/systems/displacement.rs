@@ -1,42 +1,159 @@
-use components::barrier::Barrier;
+use bracket_lib::prelude::RandomNumberGenerator;
+use components::actions::operate::Operate;
 use components::collision::Collision;
 use components::displacement::Displacement;
+use components::door::Door;
+use components::encumbrance::Encumbrance;
+use components::equipment::Equipment;
+use components::impact::Impact;
+use components::landed::Landed;
+use components::layer::Layer;
 use components::position::Position;
+use components::stepped::Stepped;
+use components::surface::Surface;
 use components::velocity::Velocity;
+use components::weight::Weight;
 use legion::systems::CommandBuffer;
 use legion::world::SubWorld;
 use legion::*;
 use map::Map;
 use std::sync::{Arc, RwLock};
+use systems::hearing::{emit, land, landing_volume};
+use vector::Vector;
 
-#[system(for_each)]
-#[read_component(Barrier)]
+#[system]
+#[write_component(Position)]
+#[write_component(Displacement)]
+#[read_component(Collision)]
+#[read_component(Velocity)]
+#[read_component(Equipment)]
+#[read_component(Weight)]
+#[read_component(Encumbrance)]
+#[read_component(Door)]
+#[read_component(Surface)]
 pub fn displacement(
     commands: &mut CommandBuffer,
-    subworld: &mut SubWorld,
-    entity: &Entity,
-    position: &mut Position,
-    displacement: &mut Displacement,
-    _collision: Option<&Collision>,
+    world: &mut SubWorld,
     #[resource] map: &Arc<RwLock<Map>>,
+    #[resource] rng: &mut RandomNumberGenerator,
 ) {
-    let mut map = map.write().unwrap();
-    let (mut left, _right) = subworld.split::<&Barrier>();
+    let mut query = <(
+        Entity,
+        &mut Position,
+        &mut Displacement,
+        Option<&Collision>,
+        Option<&Velocity>,
+        Option<&Equipment>,
+        Option<&Weight>,
+        Option<&Encumbrance>,
+    )>::query();
+    let (mut actors, rest) = world.split_for_query(&query);
+    let subworld = &rest;
 
-    if displacement.path.is_empty() {
-        commands.remove_component::<Displacement>(*entity);
-    } else {
-        let new_position = position.vector + displacement.path.pop_front().unwrap();
-        let objects_at_new_pos = &map[new_position];
+    for (entity, position, displacement, _collision, velocity, equipment, weight, encumbrance) in
+        query.iter_mut(&mut actors)
+    {
+        let mut map = map.write().unwrap();
 
-        let top_obj_entry = left.entry_mut(objects_at_new_pos[0].entity).unwrap();
+        if displacement.path.is_empty() {
+            commands.remove_component::<Displacement>(*entity);
+        } else {
+            let new_position = position.vector + displacement.path.pop_front().unwrap();
 
-        match top_obj_entry.get_component::<Barrier>() {
-            Ok(_barrier) => {
+            if map.blocks_movement(new_position) {
                 commands.remove_component::<Displacement>(*entity);
                 commands.remove_component::<Velocity>(*entity);
+
+                if velocity.is_some() {
+                    land(commands, position.vector, weight, Some(*entity));
+                    commands.add_component(*entity, Landed {});
+                }
+
+                // Walking into a door opens it, and anything thrown strikes whatever stopped it
+                let blocker = map.get_objects(new_position).and_then(|objects| {
+                    objects.iter().rev().find(|obj| obj.block_movement).cloned()
+                });
+
+                if let Some(blocker) = blocker {
+                    let is_door = subworld
+                        .entry_ref(blocker.entity)
+                        .is_ok_and(|entry| entry.get_component::<Door>().is_ok());
+
+                    if equipment.is_some() && is_door {
+                        commands.add_component(
+                            *entity,
+                            Operate {
+                                entity: blocker.entity,
+                            },
+                        );
+                    } else if velocity.is_some() {
+                        commands.add_component(
+                            *entity,
+                            Impact {
+                                entity: blocker.entity,
+                                thrower: velocity.and_then(|velocity| velocity.thrower),
+                            },
+                        );
+                    }
+                }
+            } else {
+                let from = position.vector;
+                let surfaces = |at: Vector<i32>| {
+                    map.get_objects(at)
+                        .into_iter()
+                        .flatten()
+                        .filter(|obj| obj.layer == Layer::Surface)
+                        .filter_map(|obj| {
+                            let entry = subworld.entry_ref(obj.entity).ok()?;
+                            let surface = entry.get_component::<Surface>().ok()?;
+
+                            Some(surface.clone())
+                        })
+                        .collect::<Vec<_>>()
+                };
+
+                // Coatings can hold back a step out of them, bringing anything thrown to rest
+                let drag = surfaces(from)
+                    .iter()
+                    .fold(0.0, |drag: f32, surface| drag.max(surface.drag));
+
+                // A heavy load can hold back a step too
+                let stumble = encumbrance.map_or(0.0, |encumbrance| encumbrance.stumble_chance());
+
+                if rng.rand::<f32>() < stumble {
+                    continue;
+                }
+
+                if rng.rand::<f32>() < drag {
+                    // Coming down in a pool is soft enough to break nothing
+                    if velocity.is_some() {
+                        commands.remove_component::<Displacement>(*entity);
+                        commands.remove_component::<Velocity>(*entity);
+
+                        emit(
+                            commands,
+                            from,
+                            landing_volume(weight),
+                            "a splash",
+                            Some(*entity),
+                        );
+                    }
+
+                    continue;
+                }
+
+                // Walkers slide onwards over slippery coatings, one step at a time
+                let slippery = surfaces(new_position)
+                    .iter()
+                    .any(|surface| surface.slippery);
+
+                map.move_entity(*entity, position, new_position);
+                commands.add_component(*entity, Stepped { from });
+
+                if slippery && velocity.is_none() && displacement.path.is_empty() {
+                    displacement.path.push_back(new_position - from);
+                }
             }
-            Err(_) => map.move_object_by_entity(*entity, position, new_position),
         }
     }
 }
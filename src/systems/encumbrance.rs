@@ -0,0 +1,131 @@
+use clock::Clock;
+use components::attributes::Attributes;
+use components::encumbrance::Encumbrance;
+use components::equipment::Equipment;
+use components::meta::player::Player;
+use components::quantity::Quantity;
+use components::storage::Storage;
+use components::weight::Weight;
+use interface::log::{Category, Log};
+use interface::Label;
+use label;
+use legion::systems::CommandBuffer;
+use legion::world::SubWorld;
+use legion::*;
+use std::sync::{Arc, RwLock};
+
+// The weight of an object, however many it stands for, along with everything stored inside it,
+// in grams
+pub fn total_weight<W: EntityStore>(world: &W, entity: Entity) -> u32 {
+    match world.entry_ref(entity) {
+        Ok(entry) => {
+            let count = entry
+                .get_component::<Quantity>()
+                .map_or(1, |quantity| quantity.count);
+            let own = entry
+                .get_component::<Weight>()
+                .map_or(0, |weight| weight.grams.saturating_mul(count));
+            let stored = entry.get_component::<Storage>().map_or(0, |storage| {
+                storage
+                    .objects
+                    .iter()
+                    .map(|object| total_weight(world, *object))
+                    .fold(0, u32::saturating_add)
+            });
+
+            // Weights stop at the most a u32 can hold rather than wrapping round to nothing
+            own.saturating_add(stored)
+        }
+        Err(_) => 0,
+    }
+}
+
+// The weight of everything held, worn and stored, in grams
+pub fn carried_weight<W: EntityStore>(world: &W, equipment: &Equipment) -> u32 {
+    equipment
+        .held
+        .iter()
+        .chain(equipment.storage.iter())
+        .chain(equipment.wearables.iter())
+        .map(|entity| total_weight(world, *entity))
+        .fold(0, u32::saturating_add)
+}
+
+// Weigh what each creature carries against its strength
+#[system]
+#[read_component(Attributes)]
+#[read_component(Equipment)]
+#[read_component(Encumbrance)]
+#[read_component(Player)]
+#[read_component(Weight)]
+#[read_component(Storage)]
+#[read_component(Quantity)]
+pub fn encumbrance(
+    commands: &mut CommandBuffer,
+    world: &mut SubWorld,
+    #[resource] log: &Arc<RwLock<Log>>,
+    #[resource] clock: &Clock,
+) {
+    let mut query = <(
+        Entity,
+        &Attributes,
+        &Equipment,
+        Option<&Encumbrance>,
+        Option<&Player>,
+    )>::query();
+    let (actors, rest) = world.split_for_query(&query);
+    let subworld = &rest;
+
+    for (entity, attributes, equipment, encumbrance, player) in query.iter(&actors) {
+        let current = encumbrance.cloned().unwrap_or_default();
+        let load = Encumbrance::from_load(
+            carried_weight(subworld, equipment),
+            attributes.carry_capacity(),
+        );
+
+        if encumbrance.is_some() && load == current {
+            continue;
+        }
+
+        if player.is_some() && load != current {
+            log.write()
+                .unwrap()
+                .add(clock.turn, Category::Item, label!["You are ", load.name()]);
+        }
+
+        commands.add_component(*entity, load);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weights_saturate_instead_of_overflowing() {
+        let mut world = World::default();
+        let heavy = world.push((
+            Weight {
+                grams: u32::MAX / 2,
+            },
+            Quantity { count: 3 },
+        ));
+        let light = world.push((Weight { grams: 5 }, Quantity { count: 2 }));
+        let sack = world.push((
+            Weight { grams: 100 },
+            Storage {
+                objects: vec![heavy, light],
+            },
+        ));
+        let equipment = Equipment {
+            held: Some(sack),
+            storage: Some(heavy),
+            wearables: Vec::new(),
+        };
+
+        assert_eq!(total_weight(&world, light), 10);
+        assert_eq!(total_weight(&world, heavy), u32::MAX);
+        assert_eq!(total_weight(&world, sack), u32::MAX);
+        assert_eq!(carried_weight(&world, &equipment), u32::MAX);
+    }
+}
@@ -0,0 +1,98 @@
+use bracket_lib::prelude::RandomNumberGenerator;
+use clock::Clock;
+use components::actions::examine::Examine;
+use components::attributes::Attributes;
+use components::description::Description;
+use components::glyph::Glyph;
+use components::identified::Identified;
+use components::quantity::article;
+use components::template::Template;
+use components::unidentified::Unidentified;
+use interface::log::{Category, Log};
+use interface::Label;
+use label;
+use legion::systems::CommandBuffer;
+use legion::world::SubWorld;
+use legion::*;
+use std::sync::{Arc, RwLock};
+
+// How likely studying an unidentified item is to reveal it, for each point of perception
+const EXAMINE_CHANCE: f32 = 0.03;
+
+// Study an unidentified item, which may reveal what it really is
+#[system]
+#[read_component(Examine)]
+#[read_component(Attributes)]
+#[write_component(Identified)]
+#[read_component(Description)]
+#[read_component(Glyph)]
+#[read_component(Template)]
+#[write_component(Unidentified)]
+pub fn examine(
+    commands: &mut CommandBuffer,
+    world: &mut SubWorld,
+    #[resource] log: &Arc<RwLock<Log>>,
+    #[resource] clock: &Clock,
+    #[resource] rng: &mut RandomNumberGenerator,
+) {
+    let mut log = log.write().unwrap();
+    let mut query = <(Entity, &Examine, Option<&Attributes>, &mut Identified)>::query();
+    let (mut actors, mut rest) = world.split_for_query(&query);
+    let subworld = &mut rest;
+
+    for (entity, examine, attributes, identified) in query.iter_mut(&mut actors) {
+        commands.remove_component::<Examine>(*entity);
+
+        let (name, colour, template) = match subworld.entry_ref(examine.entity) {
+            Ok(entry) => (
+                entry
+                    .get_component::<Description>()
+                    .cloned()
+                    .unwrap_or_default()
+                    .name
+                    .to_lowercase(),
+                entry
+                    .get_component::<Glyph>()
+                    .cloned()
+                    .unwrap_or_default()
+                    .colour,
+                entry
+                    .get_component::<Template>()
+                    .cloned()
+                    .unwrap_or_default(),
+            ),
+            Err(_) => continue,
+        };
+
+        // An item only gives up what it can the first time it is studied
+        let unidentified = match <&mut Unidentified>::query().get_mut(subworld, examine.entity) {
+            Ok(unidentified) if !unidentified.examined => unidentified,
+            _ => continue,
+        };
+        unidentified.examined = true;
+
+        let perception = attributes.map_or(0, |attributes| attributes.effective_perception());
+
+        if rng.rand::<f32>() < EXAMINE_CHANCE * perception as f32 {
+            identified.learn(&template.name);
+
+            let real_name = unidentified.description.name.to_lowercase();
+            log.add(
+                clock.turn,
+                Category::Item,
+                label![
+                    "You recognise the ",
+                    (name, colour),
+                    format!(" as {} ", article(&real_name)),
+                    real_name
+                ],
+            );
+        } else {
+            log.add(
+                clock.turn,
+                Category::Item,
+                label!["You cannot tell what the ", (name, colour), " is"],
+            );
+        }
+    }
+}
@@ -0,0 +1,251 @@
+use bracket_lib::prelude::RandomNumberGenerator;
+use clock::Clock;
+use components::fire::Fire;
+use components::ignites::Ignites;
+use components::layer::Layer;
+use components::position::Position;
+use components::status_effects::{Status, StatusEffect, StatusEffects};
+use components::surface::Surface;
+use components::tags::{Tags, FLAMMABLE};
+use components::weight::Weight;
+use legion::systems::CommandBuffer;
+use legion::world::SubWorld;
+use legion::*;
+use map::Map;
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+use systems::gas::release;
+use systems::status::afflict;
+use vector::Vector;
+use HAZARDS;
+
+// How many grams of each burning item a fire consumes in a turn
+const BURN_GRAMS: u32 = 50;
+
+// The chances each turn of a fire catching on each neighbouring position, and of it smoking
+const SPREAD_CHANCE: f32 = 0.25;
+const SMOKE_CHANCE: f32 = 0.5;
+const SMOKE_DENSITY: u32 = 4;
+
+// How long anything caught in a fire keeps burning after leaving it
+const BURNING_TURNS: u32 = 3;
+
+const CARDINALS: [Vector<i32>; 4] = [Vector(0, -1), Vector(1, 0), Vector(0, 1), Vector(-1, 0)];
+
+// What a position holds that matters to fire
+#[derive(Default)]
+struct Kindling {
+    fuel: Vec<Entity>,
+    doused: bool,
+    burning: bool,
+}
+
+impl Kindling {
+    fn catches(&self) -> bool {
+        !self.fuel.is_empty() && !self.doused && !self.burning
+    }
+}
+
+fn survey(subworld: &SubWorld, map: &Map, position: Vector<i32>) -> Kindling {
+    let mut kindling = Kindling::default();
+
+    for obj in map.get_objects(position).into_iter().flatten() {
+        let entry = match subworld.entry_ref(obj.entity) {
+            Ok(entry) => entry,
+            Err(_) => continue,
+        };
+
+        kindling.burning |= entry.get_component::<Fire>().is_ok();
+
+        match obj.layer {
+            Layer::Item
+                if entry
+                    .get_component::<Tags>()
+                    .is_ok_and(|tags| tags.has(FLAMMABLE)) =>
+            {
+                kindling.fuel.push(obj.entity)
+            }
+
+            Layer::Surface => {
+                if let Ok(surface) = entry.get_component::<Surface>() {
+                    if surface.flammable {
+                        kindling.fuel.push(obj.entity);
+                    }
+
+                    kindling.doused |= surface.extinguishes;
+                }
+            }
+
+            _ => {}
+        }
+    }
+
+    kindling
+}
+
+// The creatures standing at a position
+fn creatures_at(map: &Map, position: Vector<i32>) -> Vec<Entity> {
+    map.get_objects(position)
+        .into_iter()
+        .flatten()
+        .filter(|obj| obj.layer == Layer::Creature)
+        .map(|obj| obj.entity)
+        .collect()
+}
+
+// Once a turn, let fires burn through what feeds them, spread to what will catch, and smoke
+#[system]
+#[read_component(Position)]
+#[read_component(Tags)]
+#[read_component(Ignites)]
+#[write_component(Fire)]
+#[write_component(Weight)]
+#[write_component(Surface)]
+#[write_component(StatusEffects)]
+pub fn fire(
+    commands: &mut CommandBuffer,
+    subworld: &mut SubWorld,
+    #[resource] map: &Arc<RwLock<Map>>,
+    #[resource] rng: &mut RandomNumberGenerator,
+    #[resource] clock: &Clock,
+    #[state] last_turn: &mut u64,
+) {
+    if clock.turn == *last_turn {
+        return;
+    }
+    *last_turn = clock.turn;
+
+    let fires = <(Entity, &Position, &Fire)>::query()
+        .iter(subworld)
+        .map(|(entity, position, fire)| (*entity, position.vector, *fire))
+        .collect::<Vec<_>>();
+    let igniters = <(&Position, &Ignites)>::query()
+        .iter(subworld)
+        .map(|(position, _)| position.vector)
+        .collect::<Vec<_>>();
+    let burning = <(Entity, &Position, &StatusEffects)>::query()
+        .iter(subworld)
+        .filter(|(_, _, statuses)| statuses.has(Status::Burning))
+        .map(|(entity, position, _)| (*entity, position.vector))
+        .collect::<Vec<_>>();
+
+    let mut kindled = HashSet::new();
+    let mut extinguished = Vec::new();
+    let mut consumed = Vec::new();
+    let mut smoke = Vec::new();
+    let mut caught = Vec::new();
+    let mut doused = Vec::new();
+
+    {
+        let map = map.read().unwrap();
+
+        for position in igniters {
+            if survey(subworld, &map, position).catches() {
+                kindled.insert(position);
+            }
+        }
+
+        // Whatever is burning sets light to what it passes over, until something puts it out
+        for (entity, position) in burning {
+            let kindling = survey(subworld, &map, position);
+
+            if kindling.doused {
+                doused.push(entity);
+            } else if kindling.catches() {
+                kindled.insert(position);
+            }
+        }
+
+        for (entity, position, fire) in &fires {
+            let kindling = survey(subworld, &map, *position);
+
+            caught.extend(creatures_at(&map, *position));
+
+            if kindling.doused {
+                extinguished.push(*entity);
+                continue;
+            }
+
+            // Fires with nothing left to feed on die down
+            if kindling.fuel.is_empty() {
+                if fire.intensity <= 1 {
+                    extinguished.push(*entity);
+                } else if let Ok(fire) = <&mut Fire>::query().get_mut(subworld, *entity) {
+                    fire.intensity -= 1;
+                }
+
+                continue;
+            }
+
+            consumed.extend(kindling.fuel);
+
+            for direction in CARDINALS.iter() {
+                let target = *position + *direction;
+
+                if survey(subworld, &map, target).catches() && rng.rand::<f32>() < SPREAD_CHANCE {
+                    kindled.insert(target);
+                }
+            }
+
+            if rng.rand::<f32>() < SMOKE_CHANCE {
+                smoke.push(*position);
+            }
+        }
+    }
+
+    // Burning items lose weight until there is nothing left of them, and coatings burn away
+    for entity in consumed {
+        let spent = match <(Option<&mut Weight>, Option<&mut Surface>)>::query()
+            .get_mut(subworld, entity)
+        {
+            Ok((Some(weight), _)) => {
+                weight.grams = weight.grams.saturating_sub(BURN_GRAMS);
+                weight.grams == 0
+            }
+
+            Ok((None, Some(surface))) => {
+                surface.volume = surface.volume.saturating_sub(1);
+                surface.volume == 0
+            }
+
+            _ => false,
+        };
+
+        if spent {
+            map.write().unwrap().remove(entity);
+            commands.remove(entity);
+        }
+    }
+
+    for entity in extinguished {
+        map.write().unwrap().remove(entity);
+        commands.remove(entity);
+    }
+
+    for entity in caught {
+        afflict(
+            commands,
+            subworld,
+            entity,
+            [StatusEffect {
+                status: Status::Burning,
+                turns: BURNING_TURNS,
+                magnitude: 1,
+            }],
+        );
+    }
+
+    for entity in doused {
+        if let Ok(statuses) = <&mut StatusEffects>::query().get_mut(subworld, entity) {
+            statuses.cure(Status::Burning);
+        }
+    }
+
+    for position in kindled {
+        HAZARDS["fire"].spawn(commands, map.clone(), position);
+    }
+
+    for position in smoke {
+        release(commands, map.clone(), "smoke", position, SMOKE_DENSITY);
+    }
+}
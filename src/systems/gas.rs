@@ -0,0 +1,156 @@
+use bracket_lib::prelude::RandomNumberGenerator;
+use clock::Clock;
+use components::gas::Gas;
+use components::opaque::Opaque;
+use components::position::Position;
+use legion::systems::CommandBuffer;
+use legion::world::SubWorld;
+use legion::*;
+use map::Map;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use vector::Vector;
+use HAZARDS;
+
+// Gases that hide what lies behind them only do so at this density or more
+const OPAQUE_DENSITY: u32 = 3;
+
+const CARDINALS: [Vector<i32>; 4] = [Vector(0, -1), Vector(1, 0), Vector(0, 1), Vector(-1, 0)];
+
+// Let out a gas of the given kind and density at a position
+pub fn release(
+    commands: &mut CommandBuffer,
+    map: Arc<RwLock<Map>>,
+    kind: &str,
+    position: Vector<i32>,
+    density: u32,
+) -> Entity {
+    let hazard = &HAZARDS[kind];
+    let entity = hazard.spawn(commands, map, position);
+    let gas = hazard.gas.clone().unwrap_or_default();
+
+    if hazard.opaque.is_some() && density < OPAQUE_DENSITY {
+        commands.remove_component::<Opaque>(entity);
+    }
+
+    commands.add_component(entity, Gas { density, ..gas });
+
+    entity
+}
+
+// Once a turn, let gases drift into thinner neighbouring air and thin away
+#[system]
+#[read_component(Position)]
+#[read_component(Opaque)]
+#[write_component(Gas)]
+pub fn gas(
+    commands: &mut CommandBuffer,
+    subworld: &mut SubWorld,
+    #[resource] map: &Arc<RwLock<Map>>,
+    #[resource] rng: &mut RandomNumberGenerator,
+    #[resource] clock: &Clock,
+    #[state] last_turn: &mut u64,
+) {
+    if clock.turn == *last_turn {
+        return;
+    }
+    *last_turn = clock.turn;
+
+    let clouds = <(Entity, &Position, &Gas, Option<&Opaque>)>::query()
+        .iter(subworld)
+        .map(|(entity, position, gas, opaque)| {
+            (*entity, position.vector, gas.clone(), opaque.is_some())
+        })
+        .collect::<Vec<_>>();
+
+    // Clouds released onto the same position are merged into one
+    let mut owners: HashMap<Vector<i32>, Entity> = HashMap::new();
+    let mut densities: HashMap<Vector<i32>, u32> = HashMap::new();
+    let mut merged = Vec::new();
+
+    for (entity, position, gas, _) in &clouds {
+        *densities.entry(*position).or_insert(0) += gas.density;
+
+        if owners.contains_key(position) {
+            merged.push(*entity);
+        } else {
+            owners.insert(*position, *entity);
+        }
+    }
+
+    let mut released: HashMap<Vector<i32>, String> = HashMap::new();
+
+    {
+        let map = map.read().unwrap();
+
+        for (entity, position, gas, _) in &clouds {
+            if owners[position] != *entity {
+                continue;
+            }
+
+            let mut density = densities[position];
+            let start = rng.range(0, 4) as usize;
+
+            for index in 0..CARDINALS.len() {
+                let target = *position + CARDINALS[(start + index) % CARDINALS.len()];
+
+                if density < 2 || map.blocks_movement(target) {
+                    continue;
+                }
+
+                let neighbour = densities.get(&target).cloned().unwrap_or(0);
+
+                if neighbour + 1 < density {
+                    if neighbour == 0 && !owners.contains_key(&target) {
+                        released.insert(target, gas.kind.clone());
+                    }
+
+                    densities.insert(target, neighbour + 1);
+                    density -= 1;
+                }
+            }
+
+            if density > 0 && rng.rand::<f32>() < gas.dissipation {
+                density -= 1;
+            }
+
+            densities.insert(*position, density);
+        }
+    }
+
+    for entity in merged {
+        map.write().unwrap().remove(entity);
+        commands.remove(entity);
+    }
+
+    for (entity, position, gas, opaque) in &clouds {
+        if owners[position] != *entity {
+            continue;
+        }
+
+        let density = densities[position];
+
+        if density == 0 {
+            map.write().unwrap().remove(*entity);
+            commands.remove(*entity);
+            continue;
+        }
+
+        if let Ok(gas) = <&mut Gas>::query().get_mut(subworld, *entity) {
+            gas.density = density;
+        }
+
+        // The map picks up the change in opacity when it is next synced
+        let thick = HAZARDS[&gas.kind].opaque.is_some() && density >= OPAQUE_DENSITY;
+
+        if thick && !opaque {
+            commands.add_component(*entity, Opaque {});
+        } else if !thick && *opaque {
+            commands.remove_component::<Opaque>(*entity);
+        }
+    }
+
+    for (position, kind) in released {
+        release(commands, map.clone(), &kind, position, densities[&position]);
+    }
+}
@@ -1,98 +1,213 @@
 use components::position::Position;
 
 use components::actions::grab::Grab;
+use components::actions::operate::Operate;
+use components::attributes::Attributes;
+use components::barrier::Barrier;
 use components::description::Description;
+use components::door::Door;
 use components::equipment::Equipment;
 use components::glyph::Glyph;
+use components::layer::Layer;
+use components::opaque::Opaque;
+use components::quantity::{self, Quantity};
+use components::storage::Storage;
+use components::tags::{tagged, Tags, CONTAINER};
+use components::template::Template;
+use components::trigger::{Trigger, TriggerKind};
 use components::weight::Weight;
 
+use clock::Clock;
+use interface::log::{Category, Log};
 use interface::Label;
-use interface::List;
 use label;
 use legion::systems::CommandBuffer;
 use legion::world::SubWorld;
 use legion::*;
 use map::{Map, Object};
 use std::sync::{Arc, RwLock};
+use systems::encumbrance::total_weight;
+use systems::stack::stacks_with;
 
-#[system(for_each)]
+#[system]
+#[read_component(Grab)]
+#[write_component(Equipment)]
+#[read_component(Attributes)]
 #[read_component(Position)]
 #[read_component(Weight)]
 #[read_component(Glyph)]
 #[read_component(Description)]
+#[read_component(Opaque)]
+#[read_component(Barrier)]
+#[read_component(Layer)]
+#[read_component(Door)]
+#[read_component(Trigger)]
+#[read_component(Storage)]
+#[read_component(Tags)]
+#[read_component(Template)]
+#[write_component(Quantity)]
 pub fn grab(
     commands: &mut CommandBuffer,
-    subworld: &mut SubWorld,
+    world: &mut SubWorld,
     #[resource] map: &Arc<RwLock<Map>>,
-    #[resource] log: &Arc<RwLock<List>>,
-    entity: &Entity,
-    grab: &Grab,
-    equipment: &mut Equipment,
+    #[resource] log: &Arc<RwLock<Log>>,
+    #[resource] clock: &Clock,
 ) {
-    let (left, _right) = subworld.split::<(&Position, &Weight, &Glyph, &Description)>();
-    let mut log = log.write().unwrap();
-    let mut map = map.write().unwrap();
-
-    let entry = left.entry_ref(*entity).unwrap();
-    let position = entry.get_component::<Position>().unwrap();
-    let grab_entry = left.entry_ref(grab.entity).unwrap();
-    let grab_glyph = grab_entry.get_component::<Glyph>().unwrap();
-    let grab_position = grab_entry.get_component::<Position>().unwrap();
-    let grab_description = grab_entry.get_component::<Description>().unwrap();
-
-    let mut label = label!["You "];
-
-    match grab_entry.get_component::<Weight>() {
-        Ok(_grab_item) => {
-            // Remove item from map
-            let obj_stack = &mut map[grab_position.vector];
-            let entity_index = obj_stack
-                .iter()
-                .position(|obj| obj.entity == grab.entity)
-                .unwrap();
-            obj_stack.remove(entity_index);
-            commands.remove_component::<Position>(grab.entity);
-
-            if let Some(held_entity) = equipment.held {
-                let held_entry = left.entry_ref(held_entity).unwrap();
-                let held_glyph = held_entry.get_component::<Glyph>().unwrap();
-                let held_description = held_entry.get_component::<Description>().unwrap();
-
-                // Place held item on map
-                map[position.vector].insert(1, Object::new(held_entity, false, false));
-                commands.add_component::<Position>(held_entity, *position);
-
-                // Log putting down currently held item
-                label += label![
-                    "put down the ",
-                    (held_description.name.as_str(), held_glyph.colour),
-                    " and "
-                ];
+    let mut query = <(Entity, &Grab, &mut Equipment, Option<&Attributes>)>::query();
+    let (mut actors, mut rest) = world.split_for_query(&query);
+    let subworld = &mut rest;
+
+    for (entity, grab, equipment, attributes) in query.iter_mut(&mut actors) {
+        let weight = total_weight(subworld, grab.entity);
+        let count = quantity::count(subworld, grab.entity);
+        let stacks = equipment
+            .held
+            .is_some_and(|held| stacks_with(subworld, held, grab.entity));
+
+        let (left, mut right) = subworld.split::<(
+            &Position,
+            &Weight,
+            &Glyph,
+            &Description,
+            &Layer,
+            &Opaque,
+            &Barrier,
+            &Storage,
+        )>();
+        let mut log = log.write().unwrap();
+        let mut map = map.write().unwrap();
+
+        let entry = left.entry_ref(*entity).unwrap();
+        let position = entry.get_component::<Position>().unwrap();
+        let grab_entry = left.entry_ref(grab.entity).unwrap();
+        let grab_glyph = grab_entry.get_component::<Glyph>().unwrap();
+        let grab_description = grab_entry.get_component::<Description>().unwrap();
+
+        let mut label = label!["You "];
+
+        let grab_limit = attributes.cloned().unwrap_or_default().grab_limit();
+
+        match grab_entry.get_component::<Weight>() {
+            Ok(_) if weight > grab_limit => {
+                log.add(
+                    clock.turn,
+                    Category::Item,
+                    label![
+                        "The ",
+                        (grab_description.name.as_str(), grab_glyph.colour),
+                        " is too heavy to lift"
+                    ],
+                );
             }
 
-            equipment.held = Some(grab.entity);
+            // Containers are worn to store things in, if nothing else is worn for that already
+            Ok(_) if equipment.storage.is_none() && tagged(&right, grab.entity, CONTAINER) => {
+                map.remove(grab.entity);
+                commands.remove_component::<Position>(grab.entity);
 
-            label += label![
-                "pick up the ",
-                (grab_description.name.as_str(), grab_glyph.colour)
-            ];
-            log.add(label);
-        }
+                equipment.storage = Some(grab.entity);
 
-        Err(_) => {
-            if equipment.held.is_none() {
                 log.add(
-                    label
-                        + label![
-                            "touch the ",
-                            (grab_description.name.as_str(), grab_glyph.colour)
-                        ],
+                    clock.turn,
+                    Category::Item,
+                    label![
+                        "You put on the ",
+                        (grab_description.name.as_str(), grab_glyph.colour)
+                    ],
                 );
-            } else {
-                log.add(label!["Your hands are full"]);
+            }
+
+            // Picking up more of what is already held adds to it
+            Ok(_) if stacks => {
+                if let Some(held) = equipment
+                    .held
+                    .and_then(|held| <&mut Quantity>::query().get_mut(&mut right, held).ok())
+                {
+                    held.count += count;
+                }
+
+                map.remove(grab.entity);
+                commands.remove(grab.entity);
+
+                log.add(
+                    clock.turn,
+                    Category::Item,
+                    label![
+                        "You pick up the ",
+                        (
+                            Quantity { count }.label(&grab_description.name),
+                            grab_glyph.colour
+                        )
+                    ],
+                );
+            }
+
+            Ok(_grab_item) => {
+                // Remove item from map
+                map.remove(grab.entity);
+                commands.remove_component::<Position>(grab.entity);
+
+                if let Some(held_entity) = equipment.held {
+                    let held_entry = left.entry_ref(held_entity).unwrap();
+                    let held_glyph = held_entry.get_component::<Glyph>().unwrap();
+                    let held_description = held_entry.get_component::<Description>().unwrap();
+
+                    // Place held item on map, where its layer keeps it beneath whoever put it down
+                    map.place(position.vector, Object::from_entity(held_entity, &left));
+                    commands.add_component::<Position>(held_entity, *position);
+
+                    // Log putting down currently held item
+                    label += label![
+                        "put down the ",
+                        (held_description.name.as_str(), held_glyph.colour),
+                        " and "
+                    ];
+                }
+
+                equipment.held = Some(grab.entity);
+
+                label += label![
+                    "pick up the ",
+                    (
+                        Quantity { count }.label(&grab_description.name),
+                        grab_glyph.colour
+                    )
+                ];
+                log.add(clock.turn, Category::Item, label);
+            }
+
+            Err(_) => {
+                // Doors and levers are worked rather than touched
+                let operable = right.entry_ref(grab.entity).is_ok_and(|entry| {
+                    entry.get_component::<Door>().is_ok()
+                        || entry
+                            .get_component::<Trigger>()
+                            .is_ok_and(|trigger| trigger.kind == TriggerKind::Lever)
+                });
+
+                if operable {
+                    commands.add_component(
+                        *entity,
+                        Operate {
+                            entity: grab.entity,
+                        },
+                    );
+                } else if equipment.held.is_none() {
+                    log.add(
+                        clock.turn,
+                        Category::Item,
+                        label
+                            + label![
+                                "touch the ",
+                                (grab_description.name.as_str(), grab_glyph.colour)
+                            ],
+                    );
+                } else {
+                    log.add(clock.turn, Category::Item, label!["Your hands are full"]);
+                }
             }
         }
-    }
 
-    commands.remove_component::<Grab>(*entity);
+        commands.remove_component::<Grab>(*entity);
+    }
 }
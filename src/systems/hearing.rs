@@ -0,0 +1,191 @@
+use clock::Clock;
+use components::attributes::Attributes;
+use components::direction::Direction;
+use components::hearing::{Heard, Hearing};
+use components::memory::Memory;
+use components::meta::player::Player;
+use components::noise::Noise;
+use components::position::Position;
+use components::weight::Weight;
+use interface::log::{Category, Log};
+use interface::Label;
+use label;
+use legion::systems::CommandBuffer;
+use legion::world::SubWorld;
+use legion::*;
+use map::Map;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::sync::{Arc, RwLock};
+use vector::Vector;
+
+// The most that keen hearing can add to how far a noise carries
+const MAX_HEARING_BONUS: u32 = 5;
+
+// How much quieter a noise becomes for passing through anything solid, on top of the distance
+const MUFFLING: u32 = 6;
+
+// Make a noise at a position
+pub fn emit(
+    commands: &mut CommandBuffer,
+    position: Vector<i32>,
+    volume: u32,
+    description: &str,
+    source: Option<Entity>,
+) {
+    commands.push((Noise {
+        position,
+        volume,
+        description: String::from(description),
+        source,
+    },));
+}
+
+// How far the noise of something thrown coming to rest carries, which grows with its weight
+pub fn landing_volume(weight: Option<&Weight>) -> u32 {
+    (4 + weight.map_or(0, |weight| weight.grams / 100)).min(20)
+}
+
+// Make the noise of something thrown coming to rest
+pub fn land(
+    commands: &mut CommandBuffer,
+    position: Vector<i32>,
+    weight: Option<&Weight>,
+    source: Option<Entity>,
+) {
+    let description = match weight {
+        Some(weight) if weight.grams >= 1000 => "a heavy thud",
+        _ => "a clatter",
+    };
+
+    emit(
+        commands,
+        position,
+        landing_volume(weight),
+        description,
+        source,
+    );
+}
+
+// How loud a noise is at each position it carries to. Every step costs a unit of volume,
+// and solid positions cost more, so noises find their way around walls and through doors
+fn propagate(map: &Map, origin: Vector<i32>, volume: u32) -> HashMap<Vector<i32>, u32> {
+    let mut costs = HashMap::new();
+    let mut frontier = BinaryHeap::new();
+
+    costs.insert(origin, 0);
+    frontier.push(Reverse((0, origin.0, origin.1)));
+
+    while let Some(Reverse((cost, x, y))) = frontier.pop() {
+        let position = Vector(x, y);
+
+        if costs.get(&position).is_some_and(|best| *best < cost) {
+            continue;
+        }
+
+        for direction in Direction::all().iter() {
+            let next = position + direction.as_unit_vector();
+
+            let step = match map.get_objects(next) {
+                Some(_) if map.blocks_movement(next) => 1 + MUFFLING,
+                Some(_) => 1,
+                None => continue,
+            };
+            let next_cost = cost + step;
+
+            if next_cost < volume && costs.get(&next).is_none_or(|best| next_cost < *best) {
+                costs.insert(next, next_cost);
+                frontier.push(Reverse((next_cost, next.0, next.1)));
+            }
+        }
+    }
+
+    costs
+        .into_iter()
+        .map(|(position, cost)| (position, volume - cost))
+        .collect()
+}
+
+// Let everything within earshot of a noise hear it, and tell the player what they heard
+#[system(for_each)]
+#[read_component(Position)]
+#[read_component(Player)]
+#[read_component(Memory)]
+#[read_component(Attributes)]
+#[write_component(Hearing)]
+pub fn hearing(
+    commands: &mut CommandBuffer,
+    subworld: &mut SubWorld,
+    #[resource] map: &Arc<RwLock<Map>>,
+    #[resource] log: &Arc<RwLock<Log>>,
+    #[resource] clock: &Clock,
+    entity: &Entity,
+    noise: &Noise,
+) {
+    // Noises are carried far enough for the keenest listener, and each listener hears
+    // them as loud as their own hearing allows
+    let reach = propagate(
+        &map.read().unwrap(),
+        noise.position,
+        noise.volume + MAX_HEARING_BONUS,
+    );
+
+    for (listener, position, hearing, player, memory, attributes) in <(
+        Entity,
+        &Position,
+        &mut Hearing,
+        Option<&Player>,
+        Option<&Memory>,
+        Option<&Attributes>,
+    )>::query()
+    .iter_mut(subworld)
+    {
+        if noise.source == Some(*listener) {
+            continue;
+        }
+
+        let bonus = attributes.map_or(0, |attributes| attributes.hearing_bonus());
+        let loudness = reach.get(&position.vector).map_or(0, |loudness| {
+            *loudness as i32 - MAX_HEARING_BONUS as i32 + bonus.min(MAX_HEARING_BONUS as i32)
+        });
+
+        if loudness > 0 {
+            if hearing.heard.len() == Hearing::CAPACITY {
+                hearing.heard.remove(0);
+            }
+
+            hearing.heard.push(Heard {
+                position: noise.position,
+                loudness: loudness as u32,
+                description: noise.description.clone(),
+                turn: clock.turn,
+            });
+
+            // Noises made in plain sight need no telling
+            let seen = memory.is_some_and(|memory| {
+                memory
+                    .spatial
+                    .read()
+                    .unwrap()
+                    .get(noise.position)
+                    .is_some_and(|recollection| clock.since(recollection.turn) <= 1)
+            });
+
+            if player.is_some() && !seen {
+                let label = match Direction::towards(noise.position - position.vector) {
+                    Some(direction) => label![
+                        "You hear ",
+                        noise.description.as_str(),
+                        " to the ",
+                        direction.name()
+                    ],
+                    None => label!["You hear ", noise.description.as_str()],
+                };
+
+                log.write().unwrap().add(clock.turn, Category::World, label);
+            }
+        }
+    }
+
+    commands.remove(*entity);
+}
@@ -0,0 +1,47 @@
+use components::description::Description;
+use components::glyph::Glyph;
+use components::identified::Identified;
+use components::stained::Stained;
+use components::template::Template;
+use components::unidentified::Unidentified;
+use legion::systems::CommandBuffer;
+use legion::world::SubWorld;
+use legion::*;
+
+// Show items for what they really are once their kind has been identified, wherever they turn up
+#[system]
+#[read_component(Identified)]
+#[read_component(Template)]
+#[read_component(Unidentified)]
+#[write_component(Description)]
+#[write_component(Glyph)]
+#[write_component(Stained)]
+pub fn identify(commands: &mut CommandBuffer, subworld: &mut SubWorld) {
+    let known = <&Identified>::query()
+        .iter(subworld)
+        .flat_map(|identified| identified.templates.iter().cloned())
+        .collect::<Vec<_>>();
+
+    let mut query = <(
+        Entity,
+        &Template,
+        &Unidentified,
+        &mut Description,
+        &mut Glyph,
+        Option<&mut Stained>,
+    )>::query();
+
+    for (entity, template, unidentified, description, glyph, stained) in query.iter_mut(subworld) {
+        if known.contains(&template.name) {
+            *description = unidentified.description.clone();
+
+            // A stain fades back to the true colour rather than the disguise
+            match stained {
+                Some(stained) => stained.original = unidentified.colour,
+                None => glyph.colour = unidentified.colour,
+            }
+
+            commands.remove_component::<Unidentified>(*entity);
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use clock::Clock;
+use components::description::Description;
+use components::durability::Durability;
+use components::equipment::Equipment;
+use components::experience::Experience;
+use components::glyph::Glyph;
+use components::impact::Impact;
+use components::layer::Layer;
+use components::position::Position;
+use components::storage::Storage;
+use components::weight::Weight;
+use interface::log::{Category, Log};
+use interface::Label;
+use label;
+use legion::systems::CommandBuffer;
+use legion::world::SubWorld;
+use legion::*;
+use map::Map;
+use std::sync::{Arc, RwLock};
+use systems::hearing::emit;
+use FLOORS;
+
+// Every this many grams a thrown object weighs adds a point of damage to what it hits
+const GRAMS_PER_DAMAGE: u32 = 500;
+
+// How far the noises of something cracking and crumbling carry
+const CRACK_VOLUME: u32 = 10;
+const CRUMBLE_VOLUME: u32 = 16;
+
+// How far a wounded creature's cry carries
+const CRY_VOLUME: u32 = 12;
+
+// Experience earned for defeating a creature
+const DEFEAT_EXPERIENCE: u32 = 10;
+
+// Damage whatever a thrown object struck, breaking it down to its remains or slaying it
+#[system]
+#[read_component(Impact)]
+#[read_component(Weight)]
+#[write_component(Durability)]
+#[read_component(Position)]
+#[read_component(Description)]
+#[read_component(Glyph)]
+#[read_component(Layer)]
+#[read_component(Storage)]
+#[read_component(Equipment)]
+#[write_component(Experience)]
+pub fn impact(
+    commands: &mut CommandBuffer,
+    world: &mut SubWorld,
+    #[resource] map: &Arc<RwLock<Map>>,
+    #[resource] log: &Arc<RwLock<Log>>,
+    #[resource] clock: &Clock,
+) {
+    let mut query = <(Entity, &Impact, Option<&Weight>)>::query();
+    let (actors, mut rest) = world.split_for_query(&query);
+    let subworld = &mut rest;
+
+    for (entity, impact, weight) in query.iter(&actors) {
+        let damage = 1 + weight.map_or(0, |weight| weight.grams / GRAMS_PER_DAMAGE);
+        let mut defeated = false;
+
+        if let Ok(mut entry) = subworld.entry_mut(impact.entity) {
+            let name = entry
+                .get_component::<Description>()
+                .cloned()
+                .unwrap_or_default()
+                .name;
+            let colour = entry
+                .get_component::<Glyph>()
+                .cloned()
+                .unwrap_or_default()
+                .colour;
+            let position = entry.get_component::<Position>().ok().cloned();
+            let creature = entry.get_component::<Layer>() == Ok(&Layer::Creature);
+
+            // Everything carried, stored or worn, which is dropped if its bearer is destroyed
+            let mut carried = entry
+                .get_component::<Storage>()
+                .map_or(Vec::new(), |storage| storage.objects.clone());
+
+            if let Ok(equipment) = entry.get_component::<Equipment>() {
+                carried.extend(equipment.held);
+                carried.extend(equipment.storage);
+                carried.extend(equipment.wearables.iter().copied());
+            }
+
+            if let Ok(durability) = entry.get_component_mut::<Durability>() {
+                durability.hit_points = durability.hit_points.saturating_sub(damage);
+
+                let mut log = log.write().unwrap();
+
+                match position {
+                    Some(position) if durability.hit_points == 0 => {
+                        commands.remove(impact.entity);
+                        map.write().unwrap().remove(impact.entity);
+
+                        for object in carried {
+                            commands.add_component(object, position);
+                        }
+
+                        // Walls leave their remains behind, while creatures are simply gone
+                        match durability.remains.clone() {
+                            Some(remains) => {
+                                FLOORS[&remains].spawn(commands, map.clone(), position.vector);
+                                emit(
+                                    commands,
+                                    position.vector,
+                                    CRUMBLE_VOLUME,
+                                    "falling rock",
+                                    None,
+                                );
+
+                                log.add(
+                                    clock.turn,
+                                    Category::World,
+                                    label!["The ", (name.as_str(), colour), " crumbles"],
+                                );
+                            }
+
+                            None => log.add(
+                                clock.turn,
+                                Category::World,
+                                label!["The ", (name.as_str(), colour), " is slain"],
+                            ),
+                        }
+
+                        defeated = creature;
+                    }
+
+                    _ => {
+                        let (volume, sound, wound) = if creature {
+                            (CRY_VOLUME, "a pained cry", " is wounded")
+                        } else {
+                            (CRACK_VOLUME, "stone cracking", " cracks")
+                        };
+
+                        if let Some(position) = position {
+                            emit(commands, position.vector, volume, sound, None);
+                        }
+
+                        log.add(
+                            clock.turn,
+                            Category::World,
+                            label!["The ", (name.as_str(), colour), wound],
+                        )
+                    }
+                }
+            }
+        }
+
+        // Whoever threw the blow that defeated a creature learns from it
+        if defeated {
+            if let Some(mut thrower) = impact
+                .thrower
+                .and_then(|thrower| subworld.entry_mut(thrower).ok())
+            {
+                if let Ok(experience) = thrower.get_component_mut::<Experience>() {
+                    experience.gain(DEFEAT_EXPERIENCE);
+                }
+            }
+        }
+
+        commands.remove_component::<Impact>(*entity);
+    }
+}
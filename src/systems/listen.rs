@@ -0,0 +1,43 @@
+use clock::Clock;
+use components::direction::Direction;
+use components::displacement::Displacement;
+use components::hearing::Hearing;
+use components::meta::player::Player;
+use components::position::Position;
+use legion::systems::CommandBuffer;
+use legion::world::SubWorld;
+use legion::*;
+
+// Once a turn, empty out what everything has heard. Creatures step towards the loudest noise to
+// see what made it, while the player has already been told of it in the log
+#[system]
+#[read_component(Position)]
+#[read_component(Player)]
+#[write_component(Hearing)]
+pub fn listen(
+    commands: &mut CommandBuffer,
+    subworld: &mut SubWorld,
+    #[resource] clock: &Clock,
+    #[state] last_turn: &mut u64,
+) {
+    if clock.turn == *last_turn {
+        return;
+    }
+    *last_turn = clock.turn;
+
+    for (entity, position, hearing, player) in
+        <(Entity, &Position, &mut Hearing, Option<&Player>)>::query().iter_mut(subworld)
+    {
+        let loudest = hearing.drain().into_iter().next();
+
+        if player.is_some() {
+            continue;
+        }
+
+        if let Some(direction) =
+            loudest.and_then(|heard| Direction::towards(heard.position - position.vector))
+        {
+            commands.add_component(*entity, Displacement::from(direction));
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use bracket_lib::prelude::RandomNumberGenerator;
+use components::loot::Loot;
+use components::position::Position;
+use components::quantity::Quantity;
+use components::storage::Storage;
+use legion::systems::CommandBuffer;
+use legion::*;
+use map::Map;
+use std::sync::{Arc, RwLock};
+use vector::Vector;
+use ITEMS;
+
+// Fill a newly spawned container from its loot table
+#[system(for_each)]
+pub fn loot(
+    commands: &mut CommandBuffer,
+    #[resource] map: &Arc<RwLock<Map>>,
+    #[resource] rng: &mut RandomNumberGenerator,
+    entity: &Entity,
+    loot: &Loot,
+    storage: &mut Storage,
+    position: Option<&Position>,
+) {
+    commands.remove_component::<Loot>(*entity);
+
+    // Rolls for the same item are gathered together, so that stackable items make one stack
+    let mut rolled: Vec<(&str, u32)> = Vec::new();
+
+    for _ in 0..loot.rolls {
+        let choice = match rng.random_slice_entry(&loot.choices) {
+            Some(choice) => choice,
+            None => break,
+        };
+
+        if rng.rand::<f32>() >= choice.probability {
+            continue;
+        }
+
+        let count = rng.range(1, choice.most + 1);
+
+        match rolled.iter_mut().find(|(name, _)| *name == choice.name) {
+            Some((_, total)) => *total += count,
+            None => rolled.push((&choice.name, count)),
+        }
+    }
+
+    // Whatever is spawned is taken straight off the map and into the container
+    let at = position.map_or(Vector::default(), |position| position.vector);
+
+    for (name, count) in rolled {
+        let item = match ITEMS.get(name) {
+            Some(item) => item,
+            None => continue,
+        };
+
+        let spawned = if item.quantity.is_some() {
+            let spawned = item.spawn(commands, map.clone(), at);
+            commands.add_component(spawned, Quantity { count });
+            vec![spawned]
+        } else {
+            (0..count)
+                .map(|_| item.spawn(commands, map.clone(), at))
+                .collect()
+        };
+
+        for object in spawned {
+            map.write().unwrap().remove(object);
+            commands.remove_component::<Position>(object);
+            storage.objects.push(object);
+        }
+    }
+}
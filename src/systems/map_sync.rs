@@ -0,0 +1,25 @@
+use components::barrier::Barrier;
+use components::layer::Layer;
+use components::opaque::Opaque;
+use components::position::Position;
+use legion::*;
+use map::{Map, Object};
+use std::sync::{Arc, RwLock};
+
+// Keep the map in line with entities whose position or blocking components have changed.
+// Adding or removing a component moves the entity's Position too, so only it is watched
+#[system(for_each)]
+#[filter(maybe_changed::<Position>())]
+pub fn map_sync(
+    #[resource] map: &Arc<RwLock<Map>>,
+    entity: &Entity,
+    position: &Position,
+    layer: Option<&Layer>,
+    opaque: Option<&Opaque>,
+    barrier: Option<&Barrier>,
+) {
+    map.write().unwrap().sync(
+        position.vector,
+        Object::from_components(*entity, layer, opaque, barrier),
+    );
+}
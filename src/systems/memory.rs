@@ -0,0 +1,72 @@
+use bracket_lib::prelude::field_of_view;
+use clock::Clock;
+use components::experience::Experience;
+use components::glyph::Glyph;
+use components::layer::Layer;
+use components::memory::{Memory, Recollection};
+use components::position::Position;
+use components::status_effects::StatusEffects;
+use legion::world::SubWorld;
+use legion::*;
+use map::{Map, Object};
+use std::ops::Deref;
+use std::sync::{Arc, RwLock};
+use vector::Vector;
+use FOV_RADIUS;
+
+// How many newly seen tiles are worth a point of experience
+const DISCOVERY_TILES: u32 = 20;
+
+// Record what each remembering entity can currently see
+#[system(for_each)]
+#[read_component(Glyph)]
+pub fn memory(
+    subworld: &SubWorld,
+    #[resource] map: &Arc<RwLock<Map>>,
+    #[resource] clock: &Clock,
+    position: &Position,
+    memory: &Memory,
+    mut experience: Option<&mut Experience>,
+    statuses: Option<&StatusEffects>,
+) {
+    let map = map.read().unwrap();
+    let mut spatial = memory.spatial.write().unwrap();
+    let glyph_of = |obj: &Object| {
+        subworld
+            .entry_ref(obj.entity)
+            .ok()
+            .and_then(|entry| entry.get_component::<Glyph>().ok().cloned())
+    };
+
+    let radius = statuses.map_or(FOV_RADIUS, |statuses| statuses.sight(FOV_RADIUS));
+
+    for point in field_of_view(position.vector.into(), radius, map.deref()) {
+        let point: Vector<i32> = point.into();
+
+        // Terrain and features are remembered as the ground
+        if let Some(terrain) = map.top_beneath(point, Layer::Item).and_then(glyph_of) {
+            // Exploring is its own reward
+            if let Some(experience) = experience.as_deref_mut() {
+                if spatial.get(point).is_none() {
+                    experience.discovered += 1;
+
+                    if experience.discovered % DISCOVERY_TILES == 0 {
+                        experience.gain(1);
+                    }
+                }
+            }
+
+            // Only items are remembered; creatures are expected to move
+            let item = map.top_in(point, Layer::Item).and_then(glyph_of);
+
+            spatial.remember(
+                point,
+                Recollection {
+                    terrain,
+                    item,
+                    turn: clock.turn,
+                },
+            );
+        }
+    }
+}
@@ -1,5 +1,34 @@
+pub mod buy;
+pub mod camera;
+pub mod consistency;
+pub mod consume;
+pub mod craft;
 pub mod displacement;
+pub mod encumbrance;
+pub mod examine;
+pub mod fire;
+pub mod gas;
 pub mod grab;
+pub mod hearing;
+pub mod identify;
+pub mod impact;
+pub mod listen;
+pub mod loot;
+pub mod map_sync;
+pub mod memory;
+pub mod needs;
+pub mod operate;
+pub mod pressure_plate;
+pub mod sell;
+pub mod shatter;
 pub mod spawn;
+pub mod spoilage;
+pub mod stack;
+pub mod stain;
+pub mod status;
+pub mod stow;
+pub mod surface;
+pub mod take;
 pub mod throw;
+pub mod tread;
 pub mod velocity;
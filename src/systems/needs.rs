@@ -0,0 +1,75 @@
+use clock::Clock;
+use components::meta::player::Player;
+use components::needs::{Needs, Stage};
+use components::status_effects::{Status, StatusEffect, StatusEffects};
+use interface::log::{Category, Log};
+use interface::Label;
+use label;
+use legion::systems::CommandBuffer;
+use legion::world::SubWorld;
+use legion::*;
+use std::sync::{Arc, RwLock};
+use systems::status::afflict;
+
+// Once a turn, let hunger and thirst grow, warning the player as each gets worse
+#[system]
+#[write_component(Needs)]
+#[write_component(StatusEffects)]
+#[read_component(Player)]
+pub fn needs(
+    commands: &mut CommandBuffer,
+    subworld: &mut SubWorld,
+    #[resource] log: &Arc<RwLock<Log>>,
+    #[resource] clock: &Clock,
+    #[state] last_turn: &mut u64,
+) {
+    if clock.turn == *last_turn {
+        return;
+    }
+    *last_turn = clock.turn;
+
+    let mut log = log.write().unwrap();
+    let mut desperate = Vec::new();
+
+    for (entity, needs, player) in
+        <(Entity, &mut Needs, Option<&Player>)>::query().iter_mut(subworld)
+    {
+        let before = *needs;
+
+        needs.nutrition = needs.nutrition.saturating_sub(1);
+        needs.hydration = needs.hydration.saturating_sub(1);
+
+        if player.is_some() {
+            let worsened = [
+                (needs.hunger() > before.hunger())
+                    .then_some(needs.hunger_name())
+                    .flatten(),
+                (needs.thirst() > before.thirst())
+                    .then_some(needs.thirst_name())
+                    .flatten(),
+            ];
+
+            for name in worsened.iter().flatten() {
+                log.add(clock.turn, Category::World, label!["You are ", *name]);
+            }
+        }
+
+        // Going without for too long leaves little strength to move
+        if needs.hunger() == Stage::Desperate || needs.thirst() == Stage::Desperate {
+            desperate.push(*entity);
+        }
+    }
+
+    for entity in desperate {
+        afflict(
+            commands,
+            subworld,
+            entity,
+            [StatusEffect {
+                status: Status::Slowed,
+                turns: 2,
+                magnitude: 1,
+            }],
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use bracket_lib::prelude::RandomNumberGenerator;
+use clock::Clock;
+use components::actions::operate::Operate;
+use components::barrier::Barrier;
+use components::description::Description;
+use components::door::Door;
+use components::glyph::Glyph;
+use components::layer::Layer;
+use components::opaque::Opaque;
+use components::skills::Skills;
+use components::trigger::{Trigger, TriggerKind};
+use interface::log::{Category, Log};
+use interface::Label;
+use label;
+use legion::systems::CommandBuffer;
+use legion::world::{EntryMut, SubWorld};
+use legion::*;
+use map::Map;
+use std::sync::{Arc, RwLock};
+use systems::hearing::emit;
+
+// Open or close a door, which will not close on anything standing or lying in the doorway.
+// Returns whether the door ended up as asked
+pub fn set_door(
+    commands: &mut CommandBuffer,
+    map: &Map,
+    entity: Entity,
+    entry: &mut EntryMut,
+    open: bool,
+) -> bool {
+    let mut door = match entry.get_component::<Door>() {
+        Ok(door) => *door,
+        Err(_) => return false,
+    };
+
+    if door.open == open {
+        return true;
+    }
+
+    if !open
+        && map
+            .get_objects_at_entity(entity)
+            .is_some_and(|objects| objects.iter().any(|obj| obj.layer >= Layer::Item))
+    {
+        return false;
+    }
+
+    door.open = open;
+
+    // The map picks up the changed blocking components when it is next synced
+    if open {
+        commands.remove_component::<Barrier>(entity);
+        commands.remove_component::<Opaque>(entity);
+    } else {
+        commands.add_component(entity, Barrier {});
+        commands.add_component(entity, Opaque {});
+    }
+
+    if let Ok(glyph) = entry.get_component_mut::<Glyph>() {
+        glyph.character = if open {
+            door.open_character
+        } else {
+            door.closed_character
+        };
+    }
+
+    *entry.get_component_mut::<Door>().unwrap() = door;
+
+    true
+}
+
+fn toggle_door(
+    commands: &mut CommandBuffer,
+    map: &Map,
+    entity: Entity,
+    entry: &mut EntryMut,
+) -> bool {
+    match entry.get_component::<Door>() {
+        Ok(door) => {
+            let open = !door.open;
+
+            set_door(commands, map, entity, entry, open)
+        }
+        Err(_) => false,
+    }
+}
+
+// How far the creak of a door opening or closing carries
+const DOOR_VOLUME: u32 = 8;
+
+// The chance of picking a lock with no practice, and how much each level of skill adds
+const LOCKPICK_CHANCE: f32 = 0.2;
+const LOCKPICK_BONUS: f32 = 0.1;
+
+// Work a door or lever
+#[system]
+#[read_component(Operate)]
+#[write_component(Skills)]
+#[write_component(Door)]
+#[write_component(Glyph)]
+#[write_component(Trigger)]
+#[read_component(Description)]
+pub fn operate(
+    commands: &mut CommandBuffer,
+    world: &mut SubWorld,
+    #[resource] map: &Arc<RwLock<Map>>,
+    #[resource] log: &Arc<RwLock<Log>>,
+    #[resource] clock: &Clock,
+    #[resource] rng: &mut RandomNumberGenerator,
+) {
+    let mut query = <(Entity, &Operate, Option<&mut Skills>)>::query();
+    let (mut actors, mut rest) = world.split_for_query(&query);
+    let subworld = &mut rest;
+
+    for (entity, operate, skills) in query.iter_mut(&mut actors) {
+        let map = map.read().unwrap();
+        let mut log = log.write().unwrap();
+
+        if let Ok(mut entry) = subworld.entry_mut(operate.entity) {
+            let name = entry
+                .get_component::<Description>()
+                .cloned()
+                .unwrap_or_default()
+                .name;
+            let colour = entry
+                .get_component::<Glyph>()
+                .cloned()
+                .unwrap_or_default()
+                .colour;
+
+            let lever_targets = match entry.get_component_mut::<Trigger>() {
+                Ok(trigger) if trigger.kind == TriggerKind::Lever => {
+                    trigger.active = !trigger.active;
+
+                    Some(trigger.targets.clone())
+                }
+                _ => None,
+            };
+
+            match lever_targets {
+                Some(targets) => {
+                    log.add(
+                        clock.turn,
+                        Category::World,
+                        label!["You pull the ", (name.as_str(), colour)],
+                    );
+
+                    for target in targets {
+                        if let Ok(mut target_entry) = subworld.entry_mut(target) {
+                            toggle_door(commands, &map, target, &mut target_entry);
+                        }
+                    }
+                }
+
+                None => {
+                    let locked = entry.get_component::<Door>().is_ok_and(|door| door.locked);
+
+                    // Every attempt at a lock is practice, whether or not it gives
+                    if locked {
+                        let picked = skills.is_some_and(|skills| {
+                            let chance =
+                                LOCKPICK_CHANCE + LOCKPICK_BONUS * skills.lockpicking.level as f32;
+
+                            skills.lockpicking.practise(1);
+
+                            rng.rand::<f32>() < chance
+                        });
+
+                        if !picked {
+                            log.add(
+                                clock.turn,
+                                Category::World,
+                                label!["You fail to pick the lock"],
+                            );
+                            commands.remove_component::<Operate>(*entity);
+
+                            continue;
+                        }
+
+                        entry.get_component_mut::<Door>().unwrap().locked = false;
+
+                        log.add(clock.turn, Category::World, label!["You pick the lock"]);
+                    }
+
+                    let was_open = entry.get_component::<Door>().map(|door| door.open);
+
+                    if let Ok(was_open) = was_open {
+                        if toggle_door(commands, &map, operate.entity, &mut entry) {
+                            let verb = if was_open { "close" } else { "open" };
+
+                            if let Some(position) = map.position_of(operate.entity) {
+                                emit(
+                                    commands,
+                                    position,
+                                    DOOR_VOLUME,
+                                    "a door creaking",
+                                    Some(*entity),
+                                );
+                            }
+
+                            log.add(
+                                clock.turn,
+                                Category::World,
+                                label!["You ", verb, " the ", (name.as_str(), colour)],
+                            );
+                        } else {
+                            log.add(
+                                clock.turn,
+                                Category::World,
+                                label!["Something is in the way"],
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        commands.remove_component::<Operate>(*entity);
+    }
+}
@@ -0,0 +1,52 @@
+use components::door::Door;
+use components::glyph::Glyph;
+use components::layer::Layer;
+use components::position::Position;
+use components::trigger::{Trigger, TriggerKind};
+use legion::systems::CommandBuffer;
+use legion::world::SubWorld;
+use legion::*;
+use map::Map;
+use std::sync::{Arc, RwLock};
+use systems::hearing::emit;
+use systems::operate::set_door;
+
+// How far the click of a plate sinking carries
+const CLICK_VOLUME: u32 = 6;
+
+// Hold the doors linked to a pressure plate open for as long as anything is on it
+#[system(for_each)]
+#[write_component(Door)]
+#[write_component(Glyph)]
+pub fn pressure_plate(
+    commands: &mut CommandBuffer,
+    subworld: &mut SubWorld,
+    #[resource] map: &Arc<RwLock<Map>>,
+    position: &Position,
+    trigger: &mut Trigger,
+) {
+    if trigger.kind != TriggerKind::PressurePlate {
+        return;
+    }
+
+    let map = map.read().unwrap();
+    let pressed = map
+        .get_objects(position.vector)
+        .is_some_and(|objects| objects.iter().any(|obj| obj.layer >= Layer::Item));
+
+    if pressed == trigger.active {
+        return;
+    }
+
+    trigger.active = pressed;
+
+    if pressed {
+        emit(commands, position.vector, CLICK_VOLUME, "a click", None);
+    }
+
+    for target in &trigger.targets {
+        if let Ok(mut entry) = subworld.entry_mut(*target) {
+            set_door(commands, &map, *target, &mut entry, pressed);
+        }
+    }
+}
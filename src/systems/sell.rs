@@ -0,0 +1,147 @@
+use clock::Clock;
+use components::actions::sell::Sell;
+use components::description::Description;
+use components::equipment::Equipment;
+use components::glyph::Glyph;
+use components::perishable::Perishable;
+use components::position::Position;
+use components::quantity::{named, Quantity};
+use components::stained::Stained;
+use components::storage::Storage;
+use components::tags::{tagged, Tags, CURRENCY};
+use components::template::Template;
+use components::unidentified::Unidentified;
+use components::value::{offer, purse, Value};
+use interface::log::{Category, Log};
+use interface::Label;
+use label;
+use legion::systems::CommandBuffer;
+use legion::world::SubWorld;
+use legion::*;
+use map::Map;
+use std::sync::{Arc, RwLock};
+use systems::stow::store;
+use ITEMS;
+
+// The item template traders pay out in
+const COIN: &str = "coin";
+
+// Sell something held or stored to a trader, taking coins for it
+#[system]
+#[read_component(Sell)]
+#[write_component(Equipment)]
+#[read_component(Position)]
+#[read_component(Description)]
+#[read_component(Glyph)]
+#[read_component(Perishable)]
+#[read_component(Stained)]
+#[read_component(Tags)]
+#[read_component(Template)]
+#[read_component(Unidentified)]
+#[read_component(Value)]
+#[write_component(Storage)]
+#[write_component(Quantity)]
+pub fn sell(
+    commands: &mut CommandBuffer,
+    world: &mut SubWorld,
+    #[resource] map: &Arc<RwLock<Map>>,
+    #[resource] log: &Arc<RwLock<Log>>,
+    #[resource] clock: &Clock,
+) {
+    let mut log = log.write().unwrap();
+    let mut query = <(Entity, &Sell, &mut Equipment, &Position)>::query();
+    let (mut actors, mut rest) = world.split_for_query(&query);
+    let subworld = &mut rest;
+
+    for (entity, sell, equipment, position) in query.iter_mut(&mut actors) {
+        commands.remove_component::<Sell>(*entity);
+
+        let held = equipment.held == Some(sell.entity);
+        let stored = equipment.storage.is_some_and(|storage| {
+            <&Storage>::query()
+                .get(subworld, storage)
+                .is_ok_and(|storage| storage.objects.contains(&sell.entity))
+        });
+
+        if !held && !stored {
+            continue;
+        }
+
+        // Traders have no use for buying coins with coins
+        let currency = tagged(subworld, sell.entity, CURRENCY);
+
+        let price = match offer(subworld, sell.entity) {
+            Some(price) if !currency => price,
+            _ => {
+                log.add(
+                    clock.turn,
+                    Category::Item,
+                    label![
+                        "The ",
+                        named(subworld, sell.trader).0,
+                        " has no interest in the ",
+                        named(subworld, sell.entity)
+                    ],
+                );
+                continue;
+            }
+        };
+
+        // Coins join a stack already carried, or else go into storage, or else into the hand that
+        // held what was sold
+        let stack = purse(subworld, equipment).into_iter().next();
+
+        if stack.is_none() && equipment.storage.is_none() && !held {
+            log.add(
+                clock.turn,
+                Category::Item,
+                label!["You have nowhere to put the coins"],
+            );
+            continue;
+        }
+
+        let label = label![
+            "You sell the ",
+            named(subworld, sell.entity),
+            " to the ",
+            named(subworld, sell.trader).0,
+            format!(" for {} coins", price)
+        ];
+
+        if held {
+            equipment.held = None;
+        } else if let Some(storage) = equipment.storage {
+            if let Ok(storage) = <&mut Storage>::query().get_mut(subworld, storage) {
+                storage.objects.retain(|object| *object != sell.entity);
+            }
+        }
+
+        store(commands, subworld, sell.trader, sell.entity);
+
+        match stack {
+            Some(stack) => {
+                if let Ok(quantity) = <&mut Quantity>::query().get_mut(subworld, stack) {
+                    quantity.count += price;
+                }
+            }
+            None => {
+                // The coins are spawned at the seller's feet, then taken straight off the map
+                let coins = ITEMS[COIN].spawn(commands, map.clone(), position.vector);
+                map.write().unwrap().remove(coins);
+                commands.remove_component::<Position>(coins);
+                commands.add_component(coins, Quantity { count: price });
+
+                match equipment.storage {
+                    Some(storage) => {
+                        if let Ok(storage) = <&mut Storage>::query().get_mut(subworld, storage) {
+                            storage.objects.push(coins);
+                        }
+                    }
+                    None => equipment.held = Some(coins),
+                }
+            }
+        }
+
+        log.add(clock.turn, Category::Item, label);
+    }
+}
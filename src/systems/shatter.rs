@@ -0,0 +1,77 @@
+use clock::Clock;
+use components::description::Description;
+use components::glyph::Glyph;
+use components::landed::Landed;
+use components::meta::camera_focus::CameraFocus;
+use components::position::Position;
+use components::tags::{Tags, FRAGILE};
+use interface::log::{Category, Log};
+use interface::Label;
+use label;
+use legion::systems::CommandBuffer;
+use legion::world::SubWorld;
+use legion::*;
+use map::Map;
+use std::sync::{Arc, RwLock};
+use systems::hearing::emit;
+
+// How far the sound of something breaking carries
+const SHATTER_VOLUME: u32 = 12;
+
+// Fragile things break when they come down from being thrown
+#[system]
+#[read_component(Landed)]
+#[read_component(Position)]
+#[read_component(Tags)]
+#[read_component(Description)]
+#[read_component(Glyph)]
+#[read_component(CameraFocus)]
+pub fn shatter(
+    commands: &mut CommandBuffer,
+    world: &mut SubWorld,
+    #[resource] map: &Arc<RwLock<Map>>,
+    #[resource] log: &Arc<RwLock<Log>>,
+    #[resource] clock: &Clock,
+) {
+    let mut query = <(
+        Entity,
+        &Landed,
+        &Position,
+        Option<&Tags>,
+        Option<&Description>,
+        Option<&Glyph>,
+        Option<&CameraFocus>,
+    )>::query();
+
+    for (entity, _landed, position, tags, description, glyph, focus) in query.iter(world) {
+        // Whatever the camera followed in flight is left until the camera has been handed back
+        if focus.is_some() {
+            continue;
+        }
+
+        commands.remove_component::<Landed>(*entity);
+
+        if !tags.is_some_and(|tags| tags.has(FRAGILE)) {
+            continue;
+        }
+
+        let name = description.cloned().unwrap_or_default().name.to_lowercase();
+        let colour = glyph.cloned().unwrap_or_default().colour;
+
+        log.write().unwrap().add(
+            clock.turn,
+            Category::Item,
+            label!["The ", (name, colour), " shatters"],
+        );
+        emit(
+            commands,
+            position.vector,
+            SHATTER_VOLUME,
+            "something shattering",
+            Some(*entity),
+        );
+
+        map.write().unwrap().remove(*entity);
+        commands.remove(*entity);
+    }
+}
@@ -7,7 +7,7 @@ use std::sync::{Arc, RwLock};
 use components::spawn::{Spawn, SpawnType};
 use legion::systems::CommandBuffer;
 use legion::*;
-use {CREATURES, FLOORS, ITEMS, WALLS};
+use {CREATURES, FEATURES, FLOORS, ITEMS, WALLS};
 
 #[system(for_each)]
 pub fn spawn(
@@ -18,14 +18,21 @@ pub fn spawn(
     position: &Position,
     spawn: &Spawn,
 ) {
-    let choice = rng
-        .random_slice_entry(&spawn.choices)
-        .expect("No objects to spawn");
+    // A single roll picks at most one choice, so each is spawned at its own probability
+    // however many others there are
+    let mut roll = rng.rand::<f32>();
+    let chosen = spawn.choices.iter().find(|choice| {
+        roll -= choice.probability;
+        roll < 0.0
+    });
 
-    if rng.rand::<f32>() < choice.probability {
+    if let Some(choice) = chosen {
         match choice.ty {
             SpawnType::Floor => &FLOORS[&choice.name].spawn(commands, map.clone(), position.vector),
             SpawnType::Wall => &WALLS[&choice.name].spawn(commands, map.clone(), position.vector),
+            SpawnType::Feature => {
+                &FEATURES[&choice.name].spawn(commands, map.clone(), position.vector)
+            }
             SpawnType::Item => &ITEMS[&choice.name].spawn(commands, map.clone(), position.vector),
             SpawnType::Creature => {
                 &CREATURES[&choice.name].spawn(commands, map.clone(), position.vector)
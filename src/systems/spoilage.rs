@@ -0,0 +1,45 @@
+use clock::Clock;
+use components::description::Description;
+use components::glyph::Glyph;
+use components::perishable::Perishable;
+use legion::world::SubWorld;
+use legion::*;
+
+// How far rotting food turns towards a mouldy colour
+const ROT_COLOUR: (u8, u8, u8, u8) = (90, 110, 60, 255);
+const ROT_STRENGTH: f32 = 0.6;
+
+// Once a turn, let food go off
+#[system]
+#[write_component(Perishable)]
+#[write_component(Description)]
+#[write_component(Glyph)]
+pub fn spoilage(subworld: &mut SubWorld, #[resource] clock: &Clock, #[state] last_turn: &mut u64) {
+    if clock.turn == *last_turn {
+        return;
+    }
+    *last_turn = clock.turn;
+
+    for (perishable, description, glyph) in
+        <(&mut Perishable, &mut Description, Option<&mut Glyph>)>::query().iter_mut(subworld)
+    {
+        if perishable.spoiled {
+            continue;
+        }
+
+        perishable.turns = perishable.turns.saturating_sub(1);
+
+        if perishable.turns == 0 {
+            perishable.spoiled = true;
+            description.name = format!("Rotten {}", description.name.to_lowercase());
+
+            if let Some(glyph) = glyph {
+                glyph.colour = glyph
+                    .colour
+                    .rgba
+                    .lerp(ROT_COLOUR.into(), ROT_STRENGTH)
+                    .into();
+            }
+        }
+    }
+}
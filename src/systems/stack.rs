@@ -0,0 +1,171 @@
+use components::actions::grab::Grab;
+use components::description::Description;
+use components::glyph::Glyph;
+use components::landed::Landed;
+use components::meta::camera_focus::CameraFocus;
+use components::perishable::Perishable;
+use components::position::Position;
+use components::quantity::Quantity;
+use components::stained::Stained;
+use components::template::Template;
+use components::unidentified::Unidentified;
+use components::velocity::Velocity;
+use legion::systems::CommandBuffer;
+use legion::world::SubWorld;
+use legion::*;
+use map::Map;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use vector::Vector;
+use ITEMS;
+
+// What an item must share with another to be gathered into the same stack: what it is, what it
+// is called, and whether it has spoiled, been stained, or is yet to be identified
+#[derive(PartialEq, Eq, Hash)]
+struct Kind {
+    template: String,
+    name: String,
+    spoiled: bool,
+    stained: bool,
+    unidentified: bool,
+}
+
+fn kind<W: EntityStore>(world: &W, entity: Entity) -> Option<Kind> {
+    let entry = world.entry_ref(entity).ok()?;
+    entry.get_component::<Quantity>().ok()?;
+
+    Some(Kind {
+        template: entry.get_component::<Template>().ok()?.name.clone(),
+        name: entry.get_component::<Description>().ok()?.name.clone(),
+        spoiled: entry
+            .get_component::<Perishable>()
+            .is_ok_and(|perishable| perishable.spoiled),
+        stained: entry.get_component::<Stained>().is_ok(),
+        unidentified: entry.get_component::<Unidentified>().is_ok(),
+    })
+}
+
+// Whether two entities are items of the same kind that can be gathered into one stack
+pub fn stacks_with<W: EntityStore>(world: &W, entity: Entity, other: Entity) -> bool {
+    entity != other && kind(world, entity).is_some() && kind(world, entity) == kind(world, other)
+}
+
+// Split some of a stack off into an entity of its own at a position, leaving the rest behind.
+// The split keeps whatever has become of the stack since it was spawned
+pub fn split(
+    world: &mut World,
+    map: &Arc<RwLock<Map>>,
+    entity: Entity,
+    count: u32,
+    position: Vector<i32>,
+) -> Option<Entity> {
+    let mut entry = world.entry(entity)?;
+    let description = entry.get_component::<Description>().ok()?.clone();
+    let glyph = *entry.get_component::<Glyph>().ok()?;
+    let perishable = entry.get_component::<Perishable>().ok().copied();
+    let stained = entry.get_component::<Stained>().ok().copied();
+    let unidentified = entry.get_component::<Unidentified>().ok().cloned();
+    let item = ITEMS.get(&entry.get_component::<Template>().ok()?.name)?;
+    let quantity = entry.get_component_mut::<Quantity>().ok()?;
+
+    if count == 0 || count >= quantity.count {
+        return None;
+    }
+    quantity.count -= count;
+
+    let mut commands = CommandBuffer::new(world);
+    let split = item.spawn(&mut commands, map.clone(), position);
+    commands.add_component(split, Quantity { count });
+    commands.add_component(split, description);
+    commands.add_component(split, glyph);
+
+    match perishable {
+        Some(perishable) => commands.add_component(split, perishable),
+        None => commands.remove_component::<Perishable>(split),
+    }
+
+    match stained {
+        Some(stained) => commands.add_component(split, stained),
+        None => commands.remove_component::<Stained>(split),
+    }
+
+    match unidentified {
+        Some(unidentified) => commands.add_component(split, unidentified),
+        None => commands.remove_component::<Unidentified>(split),
+    }
+
+    commands.flush(world);
+
+    Some(split)
+}
+
+// Gather identical items lying together into a single stack
+#[system]
+#[read_component(Position)]
+#[read_component(Template)]
+#[read_component(Description)]
+#[read_component(Perishable)]
+#[read_component(Stained)]
+#[read_component(Unidentified)]
+#[read_component(Velocity)]
+#[read_component(Landed)]
+#[read_component(CameraFocus)]
+#[read_component(Grab)]
+#[write_component(Quantity)]
+pub fn stack(
+    commands: &mut CommandBuffer,
+    subworld: &mut SubWorld,
+    #[resource] map: &Arc<RwLock<Map>>,
+) {
+    let mut stacks = HashMap::new();
+    let mut merged = Vec::new();
+
+    // Things about to be picked up are kept apart, so that what was split off is what is taken
+    let grabbed = <&Grab>::query()
+        .iter(subworld)
+        .map(|grab| grab.entity)
+        .collect::<Vec<_>>();
+
+    let mut query = <(
+        Entity,
+        &Position,
+        &Quantity,
+        Option<&Velocity>,
+        Option<&Landed>,
+        Option<&CameraFocus>,
+    )>::query();
+
+    for (entity, position, quantity, velocity, landed, focus) in query.iter(subworld) {
+        // Things still in flight are left alone until they come to rest
+        if velocity.is_some() || landed.is_some() || focus.is_some() || grabbed.contains(entity) {
+            continue;
+        }
+
+        let kind = match kind(subworld, *entity) {
+            Some(kind) => (position.vector, kind),
+            None => continue,
+        };
+
+        match stacks.get(&kind) {
+            Some(stack) => merged.push((*entity, *stack, quantity.count)),
+            None => {
+                stacks.insert(kind, *entity);
+            }
+        }
+    }
+
+    if merged.is_empty() {
+        return;
+    }
+
+    let mut map = map.write().unwrap();
+
+    for (entity, stack, count) in merged {
+        if let Ok(quantity) = <&mut Quantity>::query().get_mut(subworld, stack) {
+            quantity.count += count;
+        }
+
+        map.remove(entity);
+        commands.remove(entity);
+    }
+}
@@ -0,0 +1,70 @@
+use clock::Clock;
+use components::glyph::{Colour, Glyph};
+use components::stained::Stained;
+use legion::systems::CommandBuffer;
+use legion::world::SubWorld;
+use legion::*;
+
+// How many turns a fresh stain takes to fade, and how much of the glyph's colour it covers
+pub const STAIN_TURNS: u32 = 60;
+const STAIN_STRENGTH: f32 = 0.7;
+
+fn tint(stained: &Stained) -> Colour {
+    let strength = STAIN_STRENGTH * stained.turns as f32 / STAIN_TURNS as f32;
+
+    stained
+        .original
+        .rgba
+        .lerp(stained.colour.rgba, strength)
+        .into()
+}
+
+// Stain a glyph with a colour, or freshen the stain if it is already stained
+pub fn apply_stain(
+    commands: &mut CommandBuffer,
+    entity: Entity,
+    glyph: &mut Glyph,
+    stained: Option<&mut Stained>,
+    colour: Colour,
+) {
+    let fresh = Stained {
+        original: stained
+            .as_ref()
+            .map_or(glyph.colour, |stained| stained.original),
+        colour,
+        turns: STAIN_TURNS,
+    };
+
+    glyph.colour = tint(&fresh);
+
+    match stained {
+        Some(stained) => *stained = fresh,
+        None => commands.add_component(entity, fresh),
+    }
+}
+
+// Once a turn, fade stains a little further
+#[system]
+#[write_component(Glyph)]
+#[write_component(Stained)]
+pub fn stain(
+    commands: &mut CommandBuffer,
+    subworld: &mut SubWorld,
+    #[resource] clock: &Clock,
+    #[state] last_turn: &mut u64,
+) {
+    if clock.turn == *last_turn {
+        return;
+    }
+    *last_turn = clock.turn;
+
+    for (entity, glyph, stained) in <(Entity, &mut Glyph, &mut Stained)>::query().iter_mut(subworld)
+    {
+        stained.turns = stained.turns.saturating_sub(1);
+        glyph.colour = tint(stained);
+
+        if stained.turns == 0 {
+            commands.remove_component::<Stained>(*entity);
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use clock::Clock;
+use components::attributes::Attributes;
+use components::light::Light;
+use components::meta::player::Player;
+use components::status_effects::{Status, StatusEffect, StatusEffects};
+use interface::log::{Category, Log};
+use interface::Label;
+use label;
+use legion::systems::CommandBuffer;
+use legion::world::SubWorld;
+use legion::*;
+use std::sync::{Arc, RwLock};
+
+// The light given off by whatever is glowing or burning
+const GLOW_INTENSITY: f32 = 0.6;
+const GLOW_RADIUS: i32 = 3;
+
+// Make an entity suffer statuses, stacking them with whatever it is already suffering
+pub fn afflict<W: EntityStore, I: IntoIterator<Item = StatusEffect>>(
+    commands: &mut CommandBuffer,
+    world: &mut W,
+    entity: Entity,
+    effects: I,
+) {
+    match <&mut StatusEffects>::query().get_mut(world, entity) {
+        Ok(statuses) => effects
+            .into_iter()
+            .for_each(|effect| statuses.afflict(effect)),
+        Err(_) => {
+            let mut statuses = StatusEffects::default();
+            effects
+                .into_iter()
+                .for_each(|effect| statuses.afflict(effect));
+
+            if !statuses.effects.is_empty() {
+                commands.add_component(entity, statuses);
+            }
+        }
+    }
+}
+
+// Once a turn, wear statuses down and bring what they do up to date
+#[system]
+#[write_component(StatusEffects)]
+#[write_component(Attributes)]
+#[read_component(Light)]
+#[read_component(Player)]
+pub fn status(
+    commands: &mut CommandBuffer,
+    subworld: &mut SubWorld,
+    #[resource] log: &Arc<RwLock<Log>>,
+    #[resource] clock: &Clock,
+    #[state] last_turn: &mut u64,
+) {
+    if clock.turn == *last_turn {
+        return;
+    }
+    *last_turn = clock.turn;
+
+    let mut log = log.write().unwrap();
+
+    for (entity, statuses, attributes, light, player) in <(
+        Entity,
+        &mut StatusEffects,
+        Option<&mut Attributes>,
+        Option<&Light>,
+        Option<&Player>,
+    )>::query()
+    .iter_mut(subworld)
+    {
+        statuses.tick();
+
+        if let Some(attributes) = attributes {
+            attributes.modifiers = statuses.modifiers();
+        }
+
+        // Glowing lights up whatever has no light of its own, until the glow fades
+        if statuses.shines() && light.is_none() {
+            let status = if statuses.has(Status::Burning) {
+                Status::Burning
+            } else {
+                Status::Glowing
+            };
+
+            commands.add_component(
+                *entity,
+                Light {
+                    colour: status.colour(),
+                    intensity: GLOW_INTENSITY,
+                    radius: GLOW_RADIUS,
+                },
+            );
+            statuses.lit = true;
+        } else if !statuses.shines() && statuses.lit {
+            commands.remove_component::<Light>(*entity);
+            statuses.lit = false;
+        }
+
+        // The player is told as statuses take hold and wear off
+        if player.is_some() {
+            for effect in &statuses.effects {
+                if !statuses.reported.contains(&effect.status) {
+                    log.add(
+                        clock.turn,
+                        Category::World,
+                        label!["You are ", (effect.status.name(), effect.status.colour())],
+                    );
+                }
+            }
+
+            for status in &statuses.reported {
+                if !statuses.has(*status) {
+                    log.add(
+                        clock.turn,
+                        Category::World,
+                        label!["You are no longer ", status.name()],
+                    );
+                }
+            }
+        }
+
+        statuses.reported = statuses
+            .effects
+            .iter()
+            .map(|effect| effect.status)
+            .collect();
+
+        if statuses.effects.is_empty() && !statuses.lit {
+            commands.remove_component::<StatusEffects>(*entity);
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use clock::Clock;
+use components::actions::stow::Stow;
+use components::description::Description;
+use components::equipment::Equipment;
+use components::glyph::Glyph;
+use components::quantity::{self, named, Quantity};
+use components::storage::{encloses, Storage};
+use components::template::Template;
+use interface::log::{Category, Log};
+use interface::Label;
+use label;
+use legion::systems::CommandBuffer;
+use legion::world::SubWorld;
+use legion::*;
+use std::sync::{Arc, RwLock};
+use systems::stack::stacks_with;
+
+// Put something into a container, gathering it into a matching stack if there is one there
+pub fn store<W: EntityStore>(
+    commands: &mut CommandBuffer,
+    world: &mut W,
+    container: Entity,
+    entity: Entity,
+) {
+    let stack = <&Storage>::query()
+        .get(world, container)
+        .ok()
+        .and_then(|storage| {
+            storage
+                .objects
+                .iter()
+                .copied()
+                .find(|object| stacks_with(world, *object, entity))
+        });
+
+    match stack {
+        Some(stack) => {
+            let count = quantity::count(world, entity);
+
+            if let Ok(quantity) = <&mut Quantity>::query().get_mut(world, stack) {
+                quantity.count += count;
+            }
+
+            commands.remove(entity);
+        }
+        None => {
+            if let Ok(storage) = <&mut Storage>::query().get_mut(world, container) {
+                storage.objects.push(entity);
+            }
+        }
+    }
+}
+
+// Put whatever is held into a container
+#[system(for_each)]
+#[read_component(Description)]
+#[read_component(Glyph)]
+#[read_component(Template)]
+#[write_component(Storage)]
+#[write_component(Quantity)]
+pub fn stow(
+    commands: &mut CommandBuffer,
+    subworld: &mut SubWorld,
+    #[resource] log: &Arc<RwLock<Log>>,
+    #[resource] clock: &Clock,
+    entity: &Entity,
+    stow: &Stow,
+    equipment: &mut Equipment,
+) {
+    let mut log = log.write().unwrap();
+    commands.remove_component::<Stow>(*entity);
+
+    let held = match equipment.held {
+        Some(held) => held,
+        None => {
+            log.add(
+                clock.turn,
+                Category::Item,
+                label!["You are not holding anything"],
+            );
+            return;
+        }
+    };
+
+    let container = match stow.container.or(equipment.storage) {
+        // Nothing goes into itself, or into anything it holds
+        Some(container) if encloses(subworld, held, container) => {
+            log.add(
+                clock.turn,
+                Category::Item,
+                label![
+                    "You cannot put the ",
+                    named(subworld, held),
+                    " inside itself"
+                ],
+            );
+            return;
+        }
+        Some(container) => container,
+        None => {
+            log.add(
+                clock.turn,
+                Category::Item,
+                label!["You have no place to store the ", named(subworld, held)],
+            );
+            return;
+        }
+    };
+
+    let place = if Some(container) == equipment.storage {
+        " in your "
+    } else {
+        " in the "
+    };
+    let label = label![
+        "You place the ",
+        named(subworld, held),
+        place,
+        named(subworld, container).0
+    ];
+
+    store(commands, subworld, container, held);
+    equipment.held = None;
+
+    log.add(clock.turn, Category::Item, label);
+}
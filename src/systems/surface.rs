@@ -0,0 +1,144 @@
+use bracket_lib::prelude::RandomNumberGenerator;
+use clock::Clock;
+use components::layer::Layer;
+use components::position::Position;
+use components::surface::Surface;
+use legion::systems::CommandBuffer;
+use legion::world::SubWorld;
+use legion::*;
+use map::Map;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, RwLock};
+use vector::Vector;
+use COATINGS;
+
+const CARDINALS: [Vector<i32>; 4] = [Vector(0, -1), Vector(1, 0), Vector(0, 1), Vector(-1, 0)];
+
+// Lay a coating of the given kind and volume on the floor at a position
+pub fn deposit(
+    commands: &mut CommandBuffer,
+    map: Arc<RwLock<Map>>,
+    kind: &str,
+    position: Vector<i32>,
+    volume: u32,
+) -> Entity {
+    let coating = &COATINGS[kind];
+    let entity = coating.spawn(commands, map, position);
+
+    commands.add_component(
+        entity,
+        Surface {
+            volume,
+            ..coating.surface.clone()
+        },
+    );
+
+    entity
+}
+
+// Whether a coating could lie at a position
+fn is_open_floor(map: &Map, position: Vector<i32>) -> bool {
+    map.top_in(position, Layer::Terrain).is_some() && !map.blocks_movement(position)
+}
+
+// Once a turn, let liquids flow and dry up and let growths creep outwards
+#[system]
+#[read_component(Position)]
+#[write_component(Surface)]
+pub fn surface(
+    commands: &mut CommandBuffer,
+    subworld: &mut SubWorld,
+    #[resource] map: &Arc<RwLock<Map>>,
+    #[resource] rng: &mut RandomNumberGenerator,
+    #[resource] clock: &Clock,
+    #[state] last_turn: &mut u64,
+) {
+    if clock.turn == *last_turn {
+        return;
+    }
+    *last_turn = clock.turn;
+
+    let surfaces = <(Entity, &Position, &Surface)>::query()
+        .iter(subworld)
+        .map(|(entity, position, surface)| (*entity, position.vector, surface.clone()))
+        .collect::<Vec<_>>();
+
+    let occupants = surfaces
+        .iter()
+        .map(|(entity, position, surface)| (*position, (*entity, surface.kind.as_str())))
+        .collect::<HashMap<_, _>>();
+    let mut volumes = surfaces
+        .iter()
+        .map(|(entity, _, surface)| (*entity, surface.volume))
+        .collect::<HashMap<_, _>>();
+    let mut claimed = occupants.keys().cloned().collect::<HashSet<_>>();
+    let mut spills = Vec::new();
+
+    {
+        let map = map.read().unwrap();
+
+        for (entity, position, surface) in &surfaces {
+            let mut volume = volumes[entity];
+
+            if volume > 0 && rng.rand::<f32>() < surface.evaporation {
+                volume -= 1;
+            }
+
+            // Liquids flow towards shallower neighbours of their own kind, or onto bare floor
+            if surface.spreads && volume > 1 {
+                let target = *position + CARDINALS[rng.range(0, 4) as usize];
+
+                if is_open_floor(&map, target) {
+                    match occupants.get(&target) {
+                        Some((other, kind))
+                            if *kind == surface.kind && volumes[other] + 1 < volume =>
+                        {
+                            *volumes.get_mut(other).unwrap() += 1;
+                            volume -= 1;
+                        }
+
+                        None if !claimed.contains(&target) => {
+                            let share = volume / 2;
+
+                            spills.push((target, surface.kind.clone(), share));
+                            claimed.insert(target);
+                            volume -= share;
+                        }
+
+                        _ => {}
+                    }
+                }
+            }
+
+            if surface.growth > 0.0 && rng.rand::<f32>() < surface.growth {
+                let target = *position + CARDINALS[rng.range(0, 4) as usize];
+
+                if is_open_floor(&map, target) && !claimed.contains(&target) {
+                    spills.push((target, surface.kind.clone(), 1));
+                    claimed.insert(target);
+                }
+            }
+
+            *volumes.get_mut(entity).unwrap() = volume;
+        }
+    }
+
+    for (entity, _, surface) in &surfaces {
+        let volume = volumes[entity];
+
+        if volume == surface.volume {
+            continue;
+        }
+
+        if volume == 0 {
+            map.write().unwrap().remove(*entity);
+            commands.remove(*entity);
+        } else if let Ok(surface) = <&mut Surface>::query().get_mut(subworld, *entity) {
+            surface.volume = volume;
+        }
+    }
+
+    for (position, kind, volume) in spills {
+        deposit(commands, map.clone(), &kind, position, volume);
+    }
+}
@@ -0,0 +1,100 @@
+use clock::Clock;
+use components::actions::take::Take;
+use components::attributes::Attributes;
+use components::description::Description;
+use components::equipment::Equipment;
+use components::glyph::Glyph;
+use components::quantity::{named, Quantity};
+use components::storage::Storage;
+use components::template::Template;
+use components::weight::Weight;
+use interface::log::{Category, Log};
+use interface::Label;
+use label;
+use legion::systems::CommandBuffer;
+use legion::world::SubWorld;
+use legion::*;
+use std::sync::{Arc, RwLock};
+use systems::encumbrance::total_weight;
+use systems::stow::store;
+
+// Take something out of a container, leaving whatever was held in its place
+#[system]
+#[read_component(Take)]
+#[write_component(Equipment)]
+#[read_component(Attributes)]
+#[read_component(Description)]
+#[read_component(Glyph)]
+#[read_component(Template)]
+#[read_component(Weight)]
+#[write_component(Storage)]
+#[write_component(Quantity)]
+pub fn take(
+    commands: &mut CommandBuffer,
+    world: &mut SubWorld,
+    #[resource] log: &Arc<RwLock<Log>>,
+    #[resource] clock: &Clock,
+) {
+    let mut log = log.write().unwrap();
+    let mut query = <(Entity, &Take, &mut Equipment, Option<&Attributes>)>::query();
+    let (mut actors, mut rest) = world.split_for_query(&query);
+    let subworld = &mut rest;
+
+    for (entity, take, equipment, attributes) in query.iter_mut(&mut actors) {
+        commands.remove_component::<Take>(*entity);
+
+        let grab_limit = attributes.cloned().unwrap_or_default().grab_limit();
+
+        if total_weight(subworld, take.entity) > grab_limit {
+            log.add(
+                clock.turn,
+                Category::Item,
+                label![
+                    "The ",
+                    named(subworld, take.entity),
+                    " is too heavy to lift"
+                ],
+            );
+            continue;
+        }
+
+        // Nothing can be taken out of what is held without putting it down first
+        if equipment.held == Some(take.container) {
+            log.add(clock.turn, Category::Item, label!["Your hands are full"]);
+            continue;
+        }
+
+        let mut label = label!["You "];
+        let container = named(subworld, take.container).0;
+
+        if let Some(held) = equipment.held {
+            label += label![
+                "put the ",
+                named(subworld, held),
+                " in the ",
+                container.clone(),
+                " and "
+            ];
+        }
+        label += label![
+            "take the ",
+            named(subworld, take.entity),
+            " from the ",
+            container
+        ];
+
+        match <&mut Storage>::query().get_mut(subworld, take.container) {
+            Ok(storage) if storage.objects.contains(&take.entity) => {
+                storage.objects.retain(|object| *object != take.entity)
+            }
+            _ => continue,
+        }
+
+        if let Some(held) = equipment.held {
+            store(commands, subworld, take.container, held);
+        }
+        equipment.held = Some(take.entity);
+
+        log.add(clock.turn, Category::Item, label);
+    }
+}
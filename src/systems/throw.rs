@@ -1,59 +1,134 @@
+use bracket_lib::prelude::RandomNumberGenerator;
 use components::actions::throw::Throw;
+use components::attributes::Attributes;
 use components::description::Description;
+use components::encumbrance::Encumbrance;
 use components::equipment::Equipment;
+use components::layer::Layer;
+use components::meta::camera_focus::CameraFocus;
 
 use components::position::Position;
+use components::quantity::Quantity;
+use components::skills::Skills;
+use components::storage::Storage;
 use components::velocity::Velocity;
 use components::weight::Weight;
 
-use interface::List;
+use interface::log::Log;
 
 use legion::systems::CommandBuffer;
 use legion::world::SubWorld;
 use legion::*;
 use map::{Map, Object};
 use std::sync::{Arc, RwLock};
+use systems::encumbrance::total_weight;
 
-#[system(for_each)]
+// The chance of an unpractised throw going astray, and how much each level of skill steadies it
+const VEER_CHANCE: f32 = 0.3;
+const VEER_REDUCTION: f32 = 0.05;
+
+#[system]
+#[read_component(Position)]
+#[read_component(Throw)]
+#[write_component(Equipment)]
+#[read_component(CameraFocus)]
+#[read_component(Attributes)]
+#[read_component(Encumbrance)]
+#[write_component(Skills)]
 #[read_component(Weight)]
 #[read_component(Description)]
+#[read_component(Layer)]
+#[read_component(Storage)]
+#[read_component(Quantity)]
 pub fn throw(
     commands: &mut CommandBuffer,
-    subworld: &mut SubWorld,
+    world: &mut SubWorld,
     #[resource] map: &Arc<RwLock<Map>>,
-    #[resource] log: &Arc<RwLock<List>>,
-    entity: &Entity,
-    position: &Position,
-    throw: &Throw,
-    equipment: &mut Equipment,
+    #[resource] log: &Arc<RwLock<Log>>,
+    #[resource] rng: &mut RandomNumberGenerator,
 ) {
-    let (mut left, _right) = subworld.split::<(&Weight, &Description)>();
-    let _log = log.write().unwrap();
-    let mut map = map.write().unwrap();
-
-    if let Some(held_entity) = equipment.held {
-        let new_position = position.vector;
-        let held_entry = left.entry_mut(held_entity).unwrap();
-        let _held_description = held_entry.get_component::<Description>().unwrap();
-
-        commands.add_component(
-            held_entity,
-            Position {
-                vector: new_position,
-            },
-        );
-        map[new_position].push(Object::from_entity(held_entity, &left));
-
-        commands.add_component(
-            held_entity,
-            Velocity {
-                direction: throw.direction,
-                magnitude: 1,
-            },
-        );
-
-        equipment.held = None;
-    }
+    let mut query = <(
+        Entity,
+        &Position,
+        &Throw,
+        &mut Equipment,
+        Option<&CameraFocus>,
+        Option<&Attributes>,
+        Option<&Encumbrance>,
+        Option<&mut Skills>,
+    )>::query();
+    let (mut actors, mut rest) = world.split_for_query(&query);
+    let subworld = &mut rest;
+
+    for (entity, position, throw, equipment, focus, attributes, encumbrance, skills) in
+        query.iter_mut(&mut actors)
+    {
+        let weight = equipment
+            .held
+            .map_or(0, |held| total_weight(subworld, held));
+        let (mut left, _right) = subworld.split::<(&Weight, &Description, &Layer, &Storage)>();
+        let _log = log.write().unwrap();
+        let mut map = map.write().unwrap();
+
+        if let Some(held_entity) = equipment.held {
+            let new_position = position.vector;
+            let held_entry = left.entry_mut(held_entity).unwrap();
+            let _held_description = held_entry.get_component::<Description>().unwrap();
+
+            commands.add_component(
+                held_entity,
+                Position {
+                    vector: new_position,
+                },
+            );
+            map.place(new_position, Object::from_entity(held_entity, &left));
+
+            // Heavier things fall short, as do things thrown under a heavy load
+            let range = attributes
+                .cloned()
+                .unwrap_or_default()
+                .throw_range(weight)
+                .saturating_sub(encumbrance.map_or(0, |encumbrance| encumbrance.throw_penalty()))
+                .max(1);
 
-    commands.remove_component::<Throw>(*entity);
+            // Unpractised throws may veer off to one side
+            let throwing = skills.map_or(0, |skills| {
+                skills.throwing.practise(1);
+                skills.throwing.level
+            });
+            let veer = (VEER_CHANCE - VEER_REDUCTION * throwing as f32).max(0.0);
+            let direction = if rng.rand::<f32>() < veer {
+                throw
+                    .direction
+                    .rotated(if rng.rand::<bool>() { 1 } else { -1 })
+            } else {
+                throw.direction
+            };
+
+            commands.add_component(
+                held_entity,
+                Velocity {
+                    direction,
+                    magnitude: 1,
+                    range,
+                    thrower: Some(*entity),
+                },
+            );
+
+            // Lend the camera to the thrown item to follow its flight
+            if focus.is_some() {
+                commands.remove_component::<CameraFocus>(*entity);
+                commands.add_component(
+                    held_entity,
+                    CameraFocus {
+                        returns_to: Some(*entity),
+                    },
+                );
+            }
+
+            equipment.held = throw.rest;
+        }
+
+        commands.remove_component::<Throw>(*entity);
+    }
 }
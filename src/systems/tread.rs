@@ -0,0 +1,158 @@
+use components::effects::{Effect, Effects};
+use components::equipment::Equipment;
+use components::glyph::Glyph;
+use components::layer::Layer;
+use components::position::Position;
+use components::skills::Skills;
+use components::stained::Stained;
+use components::status_effects::{StatusEffect, StatusEffects};
+use components::stepped::Stepped;
+use components::surface::Surface;
+use components::tracks::Tracks;
+use legion::systems::CommandBuffer;
+use legion::world::SubWorld;
+use legion::*;
+use map::Map;
+use std::sync::{Arc, RwLock};
+use systems::hearing::emit;
+use systems::stain::apply_stain;
+use systems::status::afflict;
+use systems::surface::deposit;
+use vector::Vector;
+
+// How many steps a tracked coating lasts on whatever carries it
+const TRACK_STEPS: u32 = 4;
+
+// How far footsteps carry, before any stealth softens them
+const FOOTSTEP_VOLUME: u32 = 5;
+
+// The coatings lying at a position, along with what they do to whatever touches them
+fn coatings_at(
+    subworld: &SubWorld,
+    map: &Arc<RwLock<Map>>,
+    position: Vector<i32>,
+) -> Vec<(Surface, Vec<Effect>)> {
+    let map = map.read().unwrap();
+
+    map.get_objects(position)
+        .into_iter()
+        .flatten()
+        .filter(|obj| obj.layer == Layer::Surface)
+        .filter_map(|obj| subworld.entry_ref(obj.entity).ok())
+        .filter_map(|entry| {
+            let surface = entry.get_component::<Surface>().ok()?.clone();
+            let touch = entry
+                .get_component::<Effects>()
+                .map_or(Vec::new(), |effects| effects.touch.clone());
+
+            Some((surface, touch))
+        })
+        .collect()
+}
+
+// Whatever steps through a coating is stained by it, and may carry it onwards
+#[system]
+#[read_component(Position)]
+#[read_component(Stepped)]
+#[write_component(Glyph)]
+#[write_component(Stained)]
+#[read_component(Tracks)]
+#[read_component(Equipment)]
+#[write_component(Skills)]
+#[read_component(Surface)]
+#[read_component(Effects)]
+#[write_component(StatusEffects)]
+pub fn tread(
+    commands: &mut CommandBuffer,
+    world: &mut SubWorld,
+    #[resource] map: &Arc<RwLock<Map>>,
+) {
+    let mut query = <(
+        Entity,
+        &Position,
+        &Stepped,
+        Option<&mut Glyph>,
+        Option<&mut Stained>,
+        Option<&Tracks>,
+        Option<&Equipment>,
+        Option<&mut Skills>,
+    )>::query();
+    let (mut actors, mut rest) = world.split_for_query(&query);
+    let subworld = &mut rest;
+
+    for (entity, position, stepped, mut glyph, mut stained, tracks, equipment, skills) in
+        query.iter_mut(&mut actors)
+    {
+        // Walkers are heard, less so the more they practise treading softly
+        if equipment.is_some() {
+            let stealth = skills.map_or(0, |skills| {
+                skills.stealth.practise(1);
+                skills.stealth.level
+            });
+
+            emit(
+                commands,
+                position.vector,
+                FOOTSTEP_VOLUME.saturating_sub(stealth / 2).max(1),
+                "footsteps",
+                Some(*entity),
+            );
+        }
+
+        let left = coatings_at(subworld, map, stepped.from);
+        let entered = coatings_at(subworld, map, position.vector);
+
+        let mut afflictions = Vec::new();
+
+        for effect in entered.iter().flat_map(|(_, touch)| touch) {
+            match effect {
+                Effect::Stain { colour } => {
+                    if let Some(glyph) = glyph.as_deref_mut() {
+                        apply_stain(commands, *entity, glyph, stained.as_deref_mut(), *colour)
+                    }
+                }
+
+                Effect::Afflict { status, turns } => afflictions.push(StatusEffect {
+                    status: *status,
+                    turns: *turns,
+                    magnitude: 1,
+                }),
+
+                // Only what is eaten or drunk feeds
+                Effect::Nourish { .. } | Effect::Quench { .. } => {}
+            }
+        }
+
+        afflict(commands, subworld, *entity, afflictions);
+
+        // Only pools are picked up, so that tracks do not go on leaving tracks of their own
+        let picked_up = left
+            .iter()
+            .find(|(surface, _)| surface.tracked && surface.volume > 1)
+            .map(|(surface, _)| surface.kind.clone());
+
+        let mut carried = match picked_up {
+            Some(kind) => Some(Tracks {
+                kind,
+                steps: TRACK_STEPS,
+            }),
+            None => tracks.cloned(),
+        };
+
+        // Bare floor is marked with a little of whatever is being carried
+        if let Some(tracks) = carried.as_mut() {
+            if entered.is_empty() {
+                deposit(commands, map.clone(), &tracks.kind, position.vector, 1);
+                tracks.steps -= 1;
+            }
+
+            if tracks.steps == 0 {
+                commands.remove_component::<Tracks>(*entity);
+            } else {
+                commands.add_component(*entity, tracks.clone());
+            }
+        }
+
+        commands.remove_component::<Stepped>(*entity);
+    }
+}
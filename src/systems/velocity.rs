@@ -1,18 +1,33 @@
 use components::displacement::Displacement;
+use components::landed::Landed;
 use components::position::Position;
 use components::velocity::Velocity;
+use components::weight::Weight;
 use legion::systems::CommandBuffer;
 use legion::Entity;
 use legion::*;
 use std::collections::LinkedList;
+use systems::hearing::land;
 
 #[system(for_each)]
 pub fn velocity(
     commands: &mut CommandBuffer,
     entity: &Entity,
-    _position: &Position,
-    velocity: &Velocity,
+    position: &Position,
+    velocity: &mut Velocity,
+    weight: Option<&Weight>,
 ) {
+    // Whatever has gone as far as it was thrown comes down where it is
+    if velocity.range == 0 {
+        commands.remove_component::<Velocity>(*entity);
+        land(commands, position.vector, weight, Some(*entity));
+        commands.add_component(*entity, Landed {});
+
+        return;
+    }
+
+    velocity.range -= 1;
+
     let mut path = LinkedList::new();
     path.push_back(velocity.direction.as_unit_vector() * velocity.magnitude);
 
@@ -2,11 +2,12 @@ use num_traits::{CheckedNeg, FromPrimitive, Num, NumAssignOps, NumOps, ToPrimiti
 use std::ops::{Add, AddAssign, Mul, MulAssign, Sub};
 
 use bracket_lib::prelude::Point;
+use serde::{Deserialize, Serialize};
 
 use components::direction::Direction;
 use components::position::Position;
 
-#[derive(Debug, Default, Hash, Eq, PartialEq, Copy, Clone)]
+#[derive(Debug, Default, Hash, Eq, PartialEq, Copy, Clone, Serialize, Deserialize)]
 pub struct Vector<T: Num + Copy>(pub T, pub T);
 
 impl<T: Num + ToPrimitive + Copy> Vector<T> {
@@ -30,8 +31,8 @@ impl<T: Num + Copy + CheckedNeg> From<Direction> for Vector<T> {
                 T::one().checked_neg().unwrap(),
             ),
             Direction::NorthEast => Vector(T::one(), T::one().checked_neg().unwrap()),
-            Direction::SouthWest => Vector(T::one(), T::one()),
-            Direction::SouthEast => Vector(T::one().checked_neg().unwrap(), T::one()),
+            Direction::SouthWest => Vector(T::one().checked_neg().unwrap(), T::one()),
+            Direction::SouthEast => Vector(T::one(), T::one()),
         }
     }
 }